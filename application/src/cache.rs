@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+pub(crate) struct TtlCache<K, V> {
+    ttl: Duration,
+    capacity: usize,
+    entries: RwLock<HashMap<K, (V, Instant)>>,
+}
+
+impl<K, V> TtlCache<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.read().ok()?;
+        match entries.get(key) {
+            Some((value, until)) if *until > Instant::now() => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: K, value: V) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        if let Ok(mut entries) = self.entries.write() {
+            let now = Instant::now();
+            if entries.len() >= self.capacity {
+                entries.retain(|_, (_, until)| *until > now);
+            }
+            if entries.len() < self.capacity {
+                entries.insert(key, (value, now + self.ttl));
+            }
+        }
+    }
+
+    pub(crate) fn remove(&self, key: &K) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.remove(key);
+        }
+    }
+
+    pub(crate) fn retain(&self, mut f: impl FnMut(&K, &V) -> bool) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|k, (v, _)| f(k, v));
+        }
+    }
+}
@@ -1,24 +1,78 @@
 use common::types::BoxError;
 use std::{error::Error, fmt};
+use validator::ValidationErrors;
 
 #[derive(Debug)]
 pub enum UseCaseError {
     AccountIdExists,
     PasswordMismatch,
     InvalidCredentials,
+    AccountDisabled,
+    AccountNotVerified,
+    TwoFactorEnabled,
+    TwoFactorNotEnabled,
+    TooManyAttempts(u64),
+    // RFC 6749 error code and description
+    OAuth(&'static str, String),
     BadRequest(String),
+    Validation(ValidationErrors),
     Unauthorized,
+    Forbidden,
+    NotFound,
     Infrastructure(BoxError),
 }
 
+impl UseCaseError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            UseCaseError::AccountIdExists => "account_exists",
+            UseCaseError::PasswordMismatch => "password_mismatch",
+            UseCaseError::InvalidCredentials => "invalid_credentials",
+            UseCaseError::AccountDisabled => "account_disabled",
+            UseCaseError::AccountNotVerified => "account_not_verified",
+            UseCaseError::TwoFactorEnabled => "two_factor_enabled",
+            UseCaseError::TwoFactorNotEnabled => "two_factor_not_enabled",
+            UseCaseError::TooManyAttempts(_) => "too_many_attempts",
+            UseCaseError::OAuth(error, _) => error,
+            UseCaseError::BadRequest(_) => "bad_request",
+            UseCaseError::Validation(_) => "validation_failed",
+            UseCaseError::Unauthorized => "unauthorized",
+            UseCaseError::Forbidden => "forbidden",
+            UseCaseError::NotFound => "not_found",
+            UseCaseError::Infrastructure(_) => "internal_error",
+        }
+    }
+}
+
 impl fmt::Display for UseCaseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             UseCaseError::AccountIdExists => write!(f, "Account ID already exists"),
             UseCaseError::PasswordMismatch => write!(f, "Passwords do not match"),
             UseCaseError::InvalidCredentials => write!(f, "Invalid account ID or password"),
+            UseCaseError::AccountDisabled => write!(f, "Account is disabled"),
+            UseCaseError::AccountNotVerified => write!(f, "Email address is not verified"),
+            UseCaseError::TwoFactorEnabled => {
+                write!(f, "Two-factor authentication is already enabled")
+            }
+            UseCaseError::TwoFactorNotEnabled => {
+                write!(f, "Two-factor authentication is not enabled")
+            }
+            UseCaseError::TooManyAttempts(retry_after) => {
+                write!(
+                    f,
+                    "Too many failed attempts, retry after {} seconds",
+                    retry_after
+                )
+            }
+            UseCaseError::OAuth(error, description) => {
+                write!(f, "OAuth error {}: {}", error, description)
+            }
             UseCaseError::BadRequest(reason) => write!(f, "Bad request: {}", reason),
+            UseCaseError::Validation(errors) => write!(f, "Validation failed: {}", errors),
             UseCaseError::Unauthorized => write!(f, "Un Authorized"),
+            UseCaseError::Forbidden => write!(f, "Forbidden"),
+            UseCaseError::NotFound => write!(f, "Not found"),
             UseCaseError::Infrastructure(e) => {
                 write!(f, "An unexpected infrastructure error occurred: {}", e)
             }
@@ -30,6 +84,7 @@ impl Error for UseCaseError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             UseCaseError::Infrastructure(e) => Some(e.as_ref()),
+            UseCaseError::Validation(e) => Some(e),
             _ => None,
         }
     }
@@ -40,3 +95,9 @@ impl From<BoxError> for UseCaseError {
         UseCaseError::Infrastructure(e)
     }
 }
+
+impl From<ValidationErrors> for UseCaseError {
+    fn from(e: ValidationErrors) -> Self {
+        UseCaseError::Validation(e)
+    }
+}
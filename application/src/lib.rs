@@ -2,5 +2,8 @@ pub mod errors;
 pub mod model;
 pub mod usecase;
 
+mod cache;
+mod password_policy;
+
 mod module;
 pub use module::{UseCaseModule, UseCaseModuleImpl};
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use domain::model::member::Role;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberDto {
+    pub account: String,
+    #[schema(value_type = Vec<String>, example = json!(["member", "admin"]))]
+    pub roles: Vec<Role>,
+    pub disabled: bool,
+    pub email: Option<String>,
+    /// False while the signup waits for its email address to be verified
+    pub verified: bool,
+    pub locked_until: Option<DateTime<Utc>>,
+    /// Time zone used to schedule recurring todos; UTC when not set
+    pub timezone: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, IntoParams, Validate)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ListMemberRequest {
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub offset: Option<i64>,
+    #[validate(range(min = 1, max = 100, message = "must be 1 to 100"))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMemberRequest {
+    pub disabled: Option<bool>,
+    #[schema(value_type = Option<Vec<String>>, example = json!(["member"]))]
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub roles: Option<Vec<Role>>,
+}
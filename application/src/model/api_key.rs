@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+pub use domain::model::api_key::Scope;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::rules;
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyRequest {
+    #[validate(
+        length(min = 1, max = 64, message = "must be 1 to 64 characters"),
+        custom(function = "rules::not_blank")
+    )]
+    pub name: String,
+    #[schema(value_type = Vec<String>, example = json!(["todo:read", "todo:write"]))]
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub scopes: Vec<Scope>,
+    /// Lifetime in seconds; defaults to `api_key.default_expire`
+    #[validate(range(min = 60, message = "must be at least 60"))]
+    pub expires_in: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyDto {
+    pub id: String,
+    pub name: String,
+    #[schema(value_type = Vec<String>, example = json!(["todo:read", "todo:write"]))]
+    pub scopes: Vec<Scope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+// The plain key is only ever returned here; afterwards only its hash is stored.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyResponse {
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKeyDto,
+}
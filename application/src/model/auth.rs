@@ -1,28 +1,157 @@
+pub use domain::model::api_key::Scope;
+pub use domain::model::member::Role;
 use serde::{Deserialize, Serialize};
+pub use simple_jwt::JwkSet;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
-#[derive(Deserialize, Clone, Debug)]
+use super::rules;
+use super::two_factor::TwoFactorChallengeResponse;
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct SignupRequest {
+    #[validate(
+        length(min = 3, max = 32, message = "must be 3 to 32 characters"),
+        custom(function = "rules::account")
+    )]
     pub account: String,
+    #[validate(
+        length(max = 128, message = "must be at most 128 characters"),
+        custom(function = "rules::password")
+    )]
     pub password: String,
+    #[validate(must_match(other = "password", message = "does not match password"))]
     pub confirmed_password: String,
+    /// Required when `signup.require_verification` is set; a verification mail is sent to it
+    #[validate(
+        length(max = 254, message = "must be at most 254 characters"),
+        email(message = "must be an email address")
+    )]
+    pub email: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SignupResponse {
     pub account: String,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct SigninRequest {
+    #[validate(length(min = 1, max = 32, message = "must be 1 to 32 characters"))]
     pub account: String,
+    #[validate(length(min = 1, max = 128, message = "must be 1 to 128 characters"))]
     pub password: String,
+    /// Receive the session as HttpOnly cookies instead of tokens (needs `session_cookie.enabled`)
+    #[serde(default)]
+    pub cookie: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SigninResponse {
     pub token: String,
+    pub refresh_token: String,
+}
+
+// Members with two-factor authentication get a challenge to complete instead of tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(untagged)]
+pub enum SigninResult {
+    Tokens(SigninResponse),
+    Challenge(TwoFactorChallengeResponse),
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct SignoutRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, max = 128, message = "must be 1 to 128 characters"))]
+    pub current_password: String,
+    #[validate(
+        length(max = 128, message = "must be at most 128 characters"),
+        custom(function = "rules::password")
+    )]
+    pub new_password: String,
+    #[validate(must_match(other = "new_password", message = "does not match newPassword"))]
+    pub confirmed_password: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgotPasswordRequest {
+    #[validate(length(min = 1, max = 32, message = "must be 1 to 32 characters"))]
+    pub account: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPasswordRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub token: String,
+    #[validate(
+        length(max = 128, message = "must be at most 128 characters"),
+        custom(function = "rules::password")
+    )]
+    pub password: String,
+    #[validate(must_match(other = "password", message = "does not match password"))]
+    pub confirmed_password: String,
+}
+
+#[derive(Deserialize, Clone, Debug, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub token: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ResendVerificationRequest {
+    #[validate(length(min = 1, max = 32, message = "must be 1 to 32 characters"))]
+    pub account: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1, max = 128, message = "must be 1 to 128 characters"))]
+    pub password: String,
+    /// TOTP code or an unused recovery code, required when two-factor authentication is enabled
+    #[validate(length(min = 6, max = 32, message = "must be 6 to 32 characters"))]
+    pub code: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTimezoneRequest {
+    /// IANA time zone name used to schedule recurring todos; UTC when omitted
+    #[schema(example = "Asia/Tokyo")]
+    #[validate(custom(function = "rules::timezone"))]
+    pub timezone: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthenticatedMember {
+    pub account: String,
+    pub jti: String,
+    pub expires_at: i64,
+    pub roles: Vec<Role>,
+    /// Set when authenticated with an API key, which may only use these scopes
+    pub scopes: Option<Vec<Scope>>,
 }
@@ -1,2 +1,8 @@
+pub mod admin;
+pub mod api_key;
 pub mod auth;
+pub mod oidc;
+mod rules;
+pub mod tag;
 pub mod todo;
+pub mod two_factor;
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use super::rules;
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOidcClientRequest {
+    #[validate(
+        length(min = 1, max = 64, message = "must be 1 to 64 characters"),
+        custom(function = "rules::not_blank")
+    )]
+    pub name: String,
+    #[schema(example = json!(["https://app.example.com/callback"]))]
+    #[validate(
+        length(min = 1, max = 10, message = "must have 1 to 10 entries"),
+        custom(function = "rules::redirect_uris")
+    )]
+    pub redirect_uris: Vec<String>,
+    /// Public clients (SPAs, native apps) get no secret and rely on PKCE alone
+    #[serde(default)]
+    pub public: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcClientDto {
+    pub client_id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub public: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// The secret is only ever returned here; afterwards only its hash is stored.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOidcClientResponse {
+    pub client_secret: Option<String>,
+    #[serde(flatten)]
+    pub client: OidcClientDto,
+}
+
+// Parameter names follow OAuth 2.0 / OpenID Connect rather than this API's camelCase.
+#[derive(Deserialize, Clone, Debug, Default, IntoParams, Validate)]
+#[into_params(parameter_in = Query)]
+pub struct AuthorizeRequest {
+    /// Must be `code`
+    pub response_type: Option<String>,
+    pub client_id: Option<String>,
+    pub redirect_uri: Option<String>,
+    /// Must include `openid`; `profile` adds `preferred_username`
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub nonce: Option<String>,
+    pub code_challenge: Option<String>,
+    /// Must be `S256`
+    pub code_challenge_method: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizeResponse {
+    /// Where the browser should go next: the client's redirect URI with `code` or `error`
+    pub redirect_to: String,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, ToSchema)]
+pub struct TokenRequest {
+    /// Must be `authorization_code`
+    pub grant_type: Option<String>,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    /// For `client_secret_post` and public clients; otherwise use HTTP Basic
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub id_token: String,
+    pub scope: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct UserInfoResponse {
+    pub sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct OpenIdConfiguration {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+    pub jwks_uri: String,
+    pub response_types_supported: Vec<String>,
+    pub grant_types_supported: Vec<String>,
+    pub subject_types_supported: Vec<String>,
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    pub scopes_supported: Vec<String>,
+    pub claims_supported: Vec<String>,
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+    pub code_challenge_methods_supported: Vec<String>,
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Datelike, Utc};
+use chrono_tz::Tz;
+use domain::model::recurrence::Recurrence;
+use validator::ValidationError;
+
+use crate::password_policy;
+
+const DUE_DATE_MIN_YEAR: i32 = 1970;
+const DUE_DATE_MAX_YEAR: i32 = 2100;
+
+pub(crate) fn account(value: &str) -> Result<(), ValidationError> {
+    if value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("account")
+            .with_message("may only contain letters, digits, '_', '-' and '.'".into()))
+    }
+}
+
+pub(crate) fn password(value: &str) -> Result<(), ValidationError> {
+    password_policy::check(value)
+        .map_err(|reason| ValidationError::new("password").with_message(reason.into()))
+}
+
+pub(crate) fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        Err(ValidationError::new("blank").with_message("must not be blank".into()))
+    } else {
+        Ok(())
+    }
+}
+
+// Commas separate tag names in the `tags` filter of the todo list.
+pub(crate) fn tag_name(value: &str) -> Result<(), ValidationError> {
+    not_blank(value)?;
+    if value.contains(',') {
+        Err(ValidationError::new("tag").with_message("must not contain ','".into()))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn tag_names(value: &[String]) -> Result<(), ValidationError> {
+    value.iter().try_for_each(|name| {
+        if name.chars().count() > 32 {
+            Err(ValidationError::new("tag").with_message("must be at most 32 characters".into()))
+        } else {
+            tag_name(name)
+        }
+    })
+}
+
+// Absolute URIs compared verbatim at authorization time, so no fragment and no whitespace.
+pub(crate) fn redirect_uris(value: &[String]) -> Result<(), ValidationError> {
+    let valid = |uri: &String| {
+        uri.split_once("://")
+            .is_some_and(|(scheme, rest)| !scheme.is_empty() && !rest.is_empty())
+            && !uri.contains('#')
+            && !uri.contains(char::is_whitespace)
+    };
+    if value.iter().all(valid) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("redirect_uri")
+            .with_message("must be absolute URIs without a fragment".into()))
+    }
+}
+
+pub(crate) fn timezone(value: &str) -> Result<(), ValidationError> {
+    value.parse::<Tz>().map(|_| ()).map_err(|_| {
+        ValidationError::new("timezone").with_message("must be an IANA time zone name".into())
+    })
+}
+
+pub(crate) fn recurrence(value: &str) -> Result<(), ValidationError> {
+    value
+        .parse::<Recurrence>()
+        .map(|_| ())
+        .map_err(|reason| ValidationError::new("recurrence").with_message(reason.into()))
+}
+
+pub(crate) fn due_date(value: &DateTime<Utc>) -> Result<(), ValidationError> {
+    if (DUE_DATE_MIN_YEAR..=DUE_DATE_MAX_YEAR).contains(&value.year()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("due_date").with_message(
+            format!("must be between {DUE_DATE_MIN_YEAR} and {DUE_DATE_MAX_YEAR}").into(),
+        ))
+    }
+}
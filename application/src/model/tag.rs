@@ -0,0 +1,42 @@
+use domain::model::tag::TagEntity;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::rules;
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagRequest {
+    #[validate(
+        length(min = 1, max = 32, message = "must be 1 to 32 characters"),
+        custom(function = "rules::tag_name")
+    )]
+    pub name: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagRequest {
+    #[validate(
+        length(min = 1, max = 32, message = "must be 1 to 32 characters"),
+        custom(function = "rules::tag_name")
+    )]
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagDto {
+    pub id: i64,
+    pub name: String,
+}
+
+impl From<TagEntity> for TagDto {
+    fn from(e: TagEntity) -> Self {
+        Self {
+            id: e.id,
+            name: e.name,
+        }
+    }
+}
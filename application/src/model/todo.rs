@@ -1,22 +1,245 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+pub use domain::model::todo::Priority;
+use domain::model::{
+    tag::TagMatch,
+    todo::{ChecklistItemEntity, TodoEntity, TodoSort},
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
+use super::rules;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTodoRequest {
-    pub account: String,
+    #[validate(custom(function = "rules::due_date"))]
+    pub due_date: DateTime<Utc>,
+    #[validate(
+        length(max = 1000, message = "must be at most 1000 characters"),
+        custom(function = "rules::not_blank")
+    )]
+    pub content: String,
+    pub complete: bool,
+    #[serde(default)]
+    pub public: bool,
+    /// Names of the member's own tags
+    #[serde(default)]
+    #[validate(
+        length(max = 20, message = "must be at most 20 tags"),
+        custom(function = "rules::tag_names")
+    )]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    #[schema(value_type = String, example = "high")]
+    pub priority: Priority,
+    /// Makes this todo a subtask of another todo of the member
+    pub parent_id: Option<i64>,
+    #[serde(default)]
+    #[validate(length(max = 50, message = "must be at most 50 items"), nested)]
+    pub checklist: Vec<ChecklistItem>,
+    /// `daily`, `weekly`, `monthly`, `yearly` or an RRULE such as `FREQ=WEEKLY;BYDAY=MO,TH`
+    #[schema(example = "FREQ=WEEKLY;BYDAY=MO,TH")]
+    #[validate(
+        length(max = 200, message = "must be at most 200 characters"),
+        custom(function = "rules::recurrence")
+    )]
+    pub recurrence: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTodoRequest {
+    #[validate(custom(function = "rules::due_date"))]
     pub due_date: DateTime<Utc>,
+    #[validate(
+        length(max = 1000, message = "must be at most 1000 characters"),
+        custom(function = "rules::not_blank")
+    )]
     pub content: String,
     pub complete: bool,
+    #[serde(default)]
+    pub public: bool,
+    /// Names of the member's own tags
+    #[serde(default)]
+    #[validate(
+        length(max = 20, message = "must be at most 20 tags"),
+        custom(function = "rules::tag_names")
+    )]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    #[schema(value_type = String, example = "high")]
+    pub priority: Priority,
+    /// Makes this todo a subtask of another todo of the member
+    pub parent_id: Option<i64>,
+    #[serde(default)]
+    #[validate(length(max = 50, message = "must be at most 50 items"), nested)]
+    pub checklist: Vec<ChecklistItem>,
+    /// `daily`, `weekly`, `monthly`, `yearly` or an RRULE such as `FREQ=WEEKLY;BYDAY=MO,TH`
+    #[schema(example = "FREQ=WEEKLY;BYDAY=MO,TH")]
+    #[validate(
+        length(max = 200, message = "must be at most 200 characters"),
+        custom(function = "rules::recurrence")
+    )]
+    pub recurrence: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchTodoRequest {
+    #[validate(custom(function = "rules::due_date"))]
+    pub due_date: Option<DateTime<Utc>>,
+    #[validate(
+        length(max = 1000, message = "must be at most 1000 characters"),
+        custom(function = "rules::not_blank")
+    )]
+    pub content: Option<String>,
+    pub complete: Option<bool>,
+    pub public: Option<bool>,
+    /// Replaces every tag of the todo when present
+    #[validate(
+        length(max = 20, message = "must be at most 20 tags"),
+        custom(function = "rules::tag_names")
+    )]
+    pub tags: Option<Vec<String>>,
+    #[schema(value_type = Option<String>, example = "high")]
+    pub priority: Option<Priority>,
+    /// `null` moves the todo back to the top level
+    #[serde(default, deserialize_with = "present")]
+    #[schema(value_type = Option<i64>)]
+    pub parent_id: Option<Option<i64>>,
+    /// Replaces the whole checklist when present
+    #[validate(length(max = 50, message = "must be at most 50 items"), nested)]
+    pub checklist: Option<Vec<ChecklistItem>>,
+    /// `null` stops the todo from repeating
+    #[serde(default, deserialize_with = "present")]
+    #[schema(value_type = Option<String>, example = "daily")]
+    #[validate(
+        length(max = 200, message = "must be at most 200 characters"),
+        custom(function = "rules::recurrence")
+    )]
+    pub recurrence: Option<Option<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    #[validate(
+        length(min = 1, max = 200, message = "must be 1 to 200 characters"),
+        custom(function = "rules::not_blank")
+    )]
+    pub content: String,
+    #[serde(default)]
+    pub checked: bool,
+}
+
+// Tells an explicit `null` (`Some(None)`) apart from a missing field (`None`).
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Deserialize, Clone, Debug, Default, IntoParams, Validate)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ListTodoRequest {
+    #[validate(length(max = 32, message = "must be at most 32 characters"))]
+    pub account: Option<String>,
+    pub complete: Option<bool>,
+    pub due_from: Option<DateTime<Utc>>,
+    pub due_to: Option<DateTime<Utc>>,
+    /// Comma-separated tag names
+    #[validate(length(max = 1000, message = "must be at most 1000 characters"))]
+    #[param(example = "work,urgent")]
+    pub tags: Option<String>,
+    /// `all` to require every tag or `any` to require one of them
+    #[serde(default)]
+    #[param(value_type = Option<String>, example = "any")]
+    pub tag_match: TagMatch,
+    /// `id` or `dueDate`
+    #[serde(default)]
+    #[param(value_type = Option<String>, example = "dueDate")]
+    pub sort: TodoSort,
+    #[serde(default)]
+    #[param(inline, value_type = Option<SortOrder>)]
+    pub order: SortOrder,
+    /// `nextCursor` of the previous page
+    pub cursor: Option<String>,
+    #[validate(range(min = 0, message = "must not be negative"))]
+    pub offset: Option<i64>,
+    #[validate(range(min = 1, max = 100, message = "must be 1 to 100"))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoPage {
+    pub items: Vec<TodoDto>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TodoDto {
     pub id: i64,
     pub account: String,
     pub due_date: DateTime<Utc>,
     pub content: String,
+    /// Derived from the subtasks while the todo has any
     pub complete: bool,
+    pub public: bool,
+    pub tags: Vec<String>,
+    #[schema(value_type = String, example = "high")]
+    pub priority: Priority,
+    pub parent_id: Option<i64>,
+    pub checklist: Vec<ChecklistItem>,
+    /// Canonical RRULE; the next occurrence is created when the todo is completed
+    #[schema(example = "FREQ=WEEKLY;BYDAY=MO,TH")]
+    pub recurrence: Option<String>,
+}
+
+impl TodoDto {
+    pub fn new(e: TodoEntity, tags: Vec<String>, checklist: Vec<ChecklistItemEntity>) -> Self {
+        Self {
+            id: e.id,
+            account: e.account,
+            due_date: e.due_date,
+            content: e.content,
+            complete: e.complete,
+            public: e.public,
+            tags,
+            priority: Priority::from_level(e.priority),
+            parent_id: e.parent_id,
+            checklist: checklist
+                .into_iter()
+                .map(|item| ChecklistItem {
+                    content: item.content,
+                    checked: item.checked,
+                })
+                .collect(),
+            recurrence: e.recurrence,
+        }
+    }
+}
+
+// A todo with the subtasks the viewer can read, nested to any depth.
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TodoTreeDto {
+    #[serde(flatten)]
+    pub todo: TodoDto,
+    #[schema(no_recursion)]
+    pub children: Vec<TodoTreeDto>,
 }
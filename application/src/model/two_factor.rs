@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorStatusResponse {
+    pub enabled: bool,
+    pub recovery_codes_remaining: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpConfirmRequest {
+    #[validate(length(equal = 6, message = "must be 6 digits"))]
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorReauthRequest {
+    #[validate(length(min = 1, max = 128, message = "must be 1 to 128 characters"))]
+    pub password: String,
+    /// Current TOTP code or an unused recovery code
+    #[validate(length(min = 6, max = 32, message = "must be 6 to 32 characters"))]
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorChallengeResponse {
+    pub challenge_token: String,
+    pub expires_in: i64,
+}
+
+#[derive(Deserialize, Clone, Debug, ToSchema, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpSigninRequest {
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub challenge_token: String,
+    /// Current TOTP code or an unused recovery code
+    #[validate(length(min = 6, max = 32, message = "must be 6 to 32 characters"))]
+    pub code: String,
+    /// Receive the session as HttpOnly cookies instead of tokens (needs `session_cookie.enabled`)
+    #[serde(default)]
+    pub cookie: bool,
+}
@@ -1,26 +1,60 @@
 use async_trait::async_trait;
 use std::sync::Arc;
 
-use crate::usecase::{auth::AuthUseCase, todo::TodoUseCase};
-use domain::UnitOfWorkProvider;
+use crate::errors::UseCaseError;
+use crate::usecase::{
+    admin::AdminUseCase, api_key::ApiKeyUseCase, auth::AuthUseCase, oidc::OidcUseCase,
+    tag::TagUseCase, todo::TodoUseCase, two_factor::TwoFactorUseCase,
+};
+use domain::{
+    UnitOfWorkProvider,
+    interface::{clock::Clock, notifier::Notifier},
+};
 
 #[async_trait]
 pub trait UseCaseModule: Send + Sync {
     fn auth(&self) -> Arc<AuthUseCase>;
     fn todo(&self) -> Arc<TodoUseCase>;
+    fn tag(&self) -> Arc<TagUseCase>;
+    fn admin(&self) -> Arc<AdminUseCase>;
+    fn two_factor(&self) -> Arc<TwoFactorUseCase>;
+    fn api_key(&self) -> Arc<ApiKeyUseCase>;
+    fn oidc(&self) -> Arc<OidcUseCase>;
 }
 
 #[derive(Clone)]
 pub struct UseCaseModuleImpl {
     auth: Arc<AuthUseCase>,
     todo: Arc<TodoUseCase>,
+    tag: Arc<TagUseCase>,
+    admin: Arc<AdminUseCase>,
+    two_factor: Arc<TwoFactorUseCase>,
+    api_key: Arc<ApiKeyUseCase>,
+    oidc: Arc<OidcUseCase>,
 }
 
 impl UseCaseModuleImpl {
-    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>) -> Self {
-        let auth = Arc::new(AuthUseCase::new(provider.clone()));
-        let todo = Arc::new(TodoUseCase::new(provider));
-        Self { auth, todo }
+    pub fn new(
+        provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+        notifier: Arc<dyn Notifier>,
+        clock: Arc<dyn Clock>,
+    ) -> Result<Self, UseCaseError> {
+        let auth = Arc::new(AuthUseCase::new(provider.clone(), notifier)?);
+        let todo = Arc::new(TodoUseCase::new(provider.clone(), clock));
+        let tag = Arc::new(TagUseCase::new(provider.clone()));
+        let admin = Arc::new(AdminUseCase::new(provider.clone(), auth.clone()));
+        let two_factor = Arc::new(TwoFactorUseCase::new(provider.clone()));
+        let api_key = Arc::new(ApiKeyUseCase::new(provider.clone()));
+        let oidc = Arc::new(OidcUseCase::new(provider, auth.clone())?);
+        Ok(Self {
+            auth,
+            todo,
+            tag,
+            admin,
+            two_factor,
+            api_key,
+            oidc,
+        })
     }
 }
 
@@ -31,4 +65,19 @@ impl UseCaseModule for UseCaseModuleImpl {
     fn todo(&self) -> Arc<TodoUseCase> {
         self.todo.clone()
     }
+    fn tag(&self) -> Arc<TagUseCase> {
+        self.tag.clone()
+    }
+    fn admin(&self) -> Arc<AdminUseCase> {
+        self.admin.clone()
+    }
+    fn two_factor(&self) -> Arc<TwoFactorUseCase> {
+        self.two_factor.clone()
+    }
+    fn api_key(&self) -> Arc<ApiKeyUseCase> {
+        self.api_key.clone()
+    }
+    fn oidc(&self) -> Arc<OidcUseCase> {
+        self.oidc.clone()
+    }
 }
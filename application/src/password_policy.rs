@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::errors::UseCaseError;
+
+// A short built-in list of the most common passwords; `password.deny_list` extends it.
+#[rustfmt::skip]
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "123456789", "12345678", "1234567890", "12345", "1234567", "111111", "000000",
+    "123123", "654321", "666666", "888888", "112233", "121212", "7777777", "987654321", "password",
+    "passw0rd", "p@ssw0rd", "p@ssword", "password1", "password12", "password123", "password1234",
+    "qwerty", "qwerty123", "qwertyuiop", "qwerty12345", "1q2w3e4r", "1q2w3e4r5t", "1qaz2wsx",
+    "zaq12wsx", "asdfghjkl", "asdfgh", "zxcvbnm", "abc123", "abcd1234", "a1b2c3d4", "iloveyou",
+    "princess", "sunshine", "football", "baseball", "basketball", "superman", "batman", "starwars",
+    "pokemon", "dragon", "monkey", "shadow", "master", "welcome", "welcome1", "letmein",
+    "trustno1", "whatever", "freedom", "charlie", "michael", "jennifer", "jessica", "computer",
+    "internet", "samsung", "google", "secret", "changeme", "default", "administrator", "admin",
+    "admin123", "root", "toor", "login", "guest", "test1234", "testtest", "hello123", "loveyou",
+    "lovely", "chocolate", "cookie", "butterfly", "liverpool", "arsenal", "chelsea", "mustang",
+    "harley", "ginger", "hunter", "killer",
+];
+
+static DENY_LIST: OnceLock<HashSet<String>> = OnceLock::new();
+
+// Loads `password.deny_list` (one password per line) on top of the built-in list.
+pub(crate) fn init() -> Result<(), UseCaseError> {
+    let mut list: HashSet<String> = COMMON_PASSWORDS.iter().map(|p| p.to_string()).collect();
+    if let Some(path) = &config::CONFIG.password.deny_list {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            UseCaseError::Infrastructure(
+                format!("failed to read password deny list '{path}': {e}").into(),
+            )
+        })?;
+        list.extend(
+            content
+                .lines()
+                .map(|l| l.trim().to_lowercase())
+                .filter(|l| !l.is_empty()),
+        );
+    }
+    let _ = DENY_LIST.set(list);
+    Ok(())
+}
+
+// Returns the reason the password is rejected, if any.
+pub(crate) fn check(password: &str) -> Result<(), String> {
+    let cfg = &config::CONFIG.password;
+    if (password.chars().count() as u64) < cfg.min_length {
+        return Err(format!("must be at least {} characters", cfg.min_length));
+    }
+    if is_common(password) {
+        return Err("is too common".to_string());
+    }
+    if entropy_bits(password) < cfg.min_entropy {
+        return Err("is too easy to guess".to_string());
+    }
+    Ok(())
+}
+
+// "Password123!" is treated like "password": digits and symbols around a common word add little.
+fn is_common(password: &str) -> bool {
+    let list = DENY_LIST.get_or_init(|| COMMON_PASSWORDS.iter().map(|p| p.to_string()).collect());
+    let lower = password.to_lowercase();
+    let core = lower.trim_matches(|c: char| !c.is_alphabetic());
+    list.contains(&lower) || (!core.is_empty() && list.contains(core))
+}
+
+// Character-pool estimate; repeated and sequential characters ("aaa", "abc", "321")
+// count for a single bit each.
+fn entropy_bits(password: &str) -> f64 {
+    let mut pool = 0u32;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password
+        .chars()
+        .any(|c| c.is_ascii_punctuation() || c == ' ')
+    {
+        pool += 33;
+    }
+    if !password.is_ascii() {
+        pool += 100;
+    }
+    let per_char = f64::from(pool.max(1)).log2();
+
+    let mut bits = 0.0;
+    let mut prev: Option<char> = None;
+    for c in password.chars() {
+        bits += match prev {
+            Some(p) if (c as i64 - p as i64).abs() <= 1 => 1.0,
+            _ => per_char,
+        };
+        prev = Some(c);
+    }
+    bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_passwords_are_caught_behind_digits_and_symbols() {
+        assert!(is_common("password"));
+        assert!(is_common("PassWord"));
+        assert!(is_common("Password123!"));
+        assert!(is_common("!!letmein2024"));
+        assert!(!is_common("correct horse battery staple"));
+        assert!(!is_common("12345!"));
+    }
+
+    #[test]
+    fn entropy_grows_with_the_character_pool() {
+        let lower = entropy_bits("zqxjwm");
+        let mixed = entropy_bits("zQxJwM");
+        let symbols = entropy_bits("zQ7!wM");
+        assert!(
+            lower < mixed && mixed < symbols,
+            "{lower} {mixed} {symbols}"
+        );
+        assert!((lower - 6.0 * 26f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn runs_of_repeated_or_sequential_characters_count_one_bit_each() {
+        let per_char = 26f64.log2();
+        assert!((entropy_bits("aaaaaaaa") - (per_char + 7.0)).abs() < 1e-9);
+        assert!((entropy_bits("abcdefgh") - (per_char + 7.0)).abs() < 1e-9);
+        assert!((entropy_bits("87654321") - (10f64.log2() + 7.0)).abs() < 1e-9);
+        assert!(entropy_bits("aaaaaaaaaaaaaaaa") < entropy_bits("Pw-Orbit-1"));
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::errors::UseCaseError;
+use crate::model::admin::{ListMemberRequest, MemberDto, UpdateMemberRequest};
+use crate::usecase::{
+    auth::{self, AuthUseCase},
+    throttle,
+};
+use domain::{
+    UnitOfWork, UnitOfWorkProvider,
+    model::member::{MemberEntity, Role},
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+pub struct AdminUseCase {
+    provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+    auth: Arc<AuthUseCase>,
+}
+
+impl AdminUseCase {
+    pub fn new(
+        provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+        auth: Arc<AuthUseCase>,
+    ) -> Self {
+        Self { provider, auth }
+    }
+
+    pub async fn list_members(
+        &self,
+        dto: ListMemberRequest,
+    ) -> Result<Vec<MemberDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let limit = dto
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+        let offset = dto.offset.unwrap_or(0).max(0);
+
+        let members = uow.member().select_page(offset, limit).await?;
+        let mut res = Vec::with_capacity(members.len());
+        for member in members {
+            res.push(to_dto(uow.as_mut(), member).await?);
+        }
+        uow.commit().await?;
+
+        Ok(res)
+    }
+
+    pub async fn find_member(&self, account: &str) -> Result<Option<MemberDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let member = uow.member().select(account).await?;
+        let res = match member {
+            Some(member) => Some(to_dto(uow.as_mut(), member).await?),
+            None => None,
+        };
+        uow.commit().await?;
+
+        Ok(res)
+    }
+
+    pub async fn update_member(
+        &self,
+        actor: &str,
+        account: &str,
+        dto: UpdateMemberRequest,
+    ) -> Result<Option<MemberDto>, UseCaseError> {
+        if actor == account
+            && (dto.disabled == Some(true)
+                || dto
+                    .roles
+                    .as_ref()
+                    .is_some_and(|r| !r.contains(&Role::Admin)))
+        {
+            return Err(UseCaseError::BadRequest(
+                "Cannot disable or demote your own account".to_string(),
+            ));
+        }
+
+        let mut uow = self.provider.begin().await?;
+
+        if uow.member().select(account).await?.is_none() {
+            return Ok(None);
+        }
+
+        if let Some(disabled) = dto.disabled {
+            uow.member().update_disabled(account, disabled).await?;
+            if disabled {
+                uow.refresh_token().revoke_account(account).await?;
+            }
+        }
+        if let Some(roles) = &dto.roles {
+            uow.member().delete_roles(account).await?;
+            for role in roles {
+                uow.member().insert_role(account, *role).await?;
+            }
+        }
+        // Access tokens carry the old roles and state: force clients to refresh or sign in again.
+        uow.revoked_token()
+            .revoke_account(account, Utc::now())
+            .await?;
+
+        let member = uow.member().select(account).await?;
+        let res = match member {
+            Some(member) => Some(to_dto(uow.as_mut(), member).await?),
+            None => None,
+        };
+        uow.commit().await?;
+        self.auth.forget_account(account);
+
+        Ok(res)
+    }
+
+    // Clears the account's failed signin counter and any backoff or lockout.
+    pub async fn unlock_member(&self, account: &str) -> Result<Option<MemberDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let member = match uow.member().select(account).await? {
+            Some(m) => m,
+            None => return Ok(None),
+        };
+        throttle::clear(uow.as_mut(), account).await?;
+        let res = to_dto(uow.as_mut(), member).await?;
+        uow.commit().await?;
+
+        Ok(Some(res))
+    }
+
+    pub async fn delete_member(
+        &self,
+        actor: &str,
+        account: &str,
+    ) -> Result<Option<MemberDto>, UseCaseError> {
+        if actor == account {
+            return Err(UseCaseError::BadRequest(
+                "Cannot delete your own account".to_string(),
+            ));
+        }
+
+        let mut uow = self.provider.begin().await?;
+
+        let member = match uow.member().select(account).await? {
+            Some(m) => m,
+            None => return Ok(None),
+        };
+        let res = to_dto(uow.as_mut(), member).await?;
+
+        auth::delete_member(uow.as_mut(), account).await?;
+
+        uow.commit().await?;
+        self.auth.forget_account(account);
+
+        Ok(Some(res))
+    }
+
+    pub async fn grant_role(&self, account: &str, role: Role) -> Result<MemberDto, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let member = match uow.member().select(account).await? {
+            Some(m) => m,
+            None => {
+                return Err(UseCaseError::BadRequest(format!(
+                    "Account '{account}' does not exist"
+                )));
+            }
+        };
+        uow.member().insert_role(account, role).await?;
+        let res = to_dto(uow.as_mut(), member).await?;
+
+        uow.commit().await?;
+
+        Ok(res)
+    }
+}
+
+async fn to_dto(uow: &mut dyn UnitOfWork, member: MemberEntity) -> Result<MemberDto, UseCaseError> {
+    let roles = uow.member().select_roles(&member.account).await?;
+    let locked_until = throttle::locked_until(uow, &member.account, Utc::now()).await?;
+    Ok(MemberDto {
+        account: member.account,
+        roles,
+        disabled: member.disabled,
+        email: member.email,
+        verified: member.verified,
+        locked_until,
+        timezone: member.timezone,
+    })
+}
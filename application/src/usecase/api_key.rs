@@ -0,0 +1,157 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::UseCaseError;
+use crate::model::api_key::{ApiKeyDto, CreateApiKeyRequest, CreateApiKeyResponse, Scope};
+use crate::model::auth::AuthenticatedMember;
+use crate::usecase::auth::{hash_token, new_token};
+use domain::{
+    UnitOfWorkProvider,
+    model::{api_key::ApiKeyEntity, member::Role},
+};
+
+pub const API_KEY_PREFIX: &str = "ak_";
+const MAX_KEYS_PER_MEMBER: usize = 20;
+// Keeps `last_used_at` roughly current without writing on every request.
+const TOUCH_INTERVAL_SECS: i64 = 60;
+
+pub struct ApiKeyUseCase {
+    provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+}
+
+impl ApiKeyUseCase {
+    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>) -> Self {
+        Self { provider }
+    }
+
+    pub async fn create(
+        &self,
+        member: &AuthenticatedMember,
+        dto: CreateApiKeyRequest,
+    ) -> Result<CreateApiKeyResponse, UseCaseError> {
+        if dto.scopes.contains(&Scope::Admin) && !member.roles.contains(&Role::Admin) {
+            return Err(UseCaseError::Forbidden);
+        }
+        let cfg = &config::CONFIG.api_key;
+        let expires_in = dto
+            .expires_in
+            .unwrap_or(cfg.default_expire.min(cfg.max_expire));
+        if expires_in > cfg.max_expire {
+            return Err(UseCaseError::BadRequest(format!(
+                "expiresIn must not exceed {} seconds",
+                cfg.max_expire
+            )));
+        }
+
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        uow.api_key().delete_expired(now).await?;
+        if uow
+            .api_key()
+            .select_by_account(&member.account)
+            .await?
+            .len()
+            >= MAX_KEYS_PER_MEMBER
+        {
+            return Err(UseCaseError::BadRequest(format!(
+                "A member can have at most {MAX_KEYS_PER_MEMBER} API keys"
+            )));
+        }
+
+        let mut scopes = dto.scopes;
+        scopes.sort_by_key(|s| s.as_str());
+        scopes.dedup();
+        let key = format!("{API_KEY_PREFIX}{}", new_token());
+        let entity = ApiKeyEntity {
+            id: Uuid::new_v4().to_string(),
+            account: member.account.clone(),
+            name: dto.name.trim().to_string(),
+            key_hash: hash_token(&key),
+            scopes: scopes
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            created_at: now,
+            expires_at: now + Duration::seconds(expires_in),
+            last_used_at: None,
+        };
+        uow.api_key().insert(&entity).await?;
+        uow.commit().await?;
+
+        Ok(CreateApiKeyResponse {
+            key,
+            api_key: to_dto(entity),
+        })
+    }
+
+    pub async fn list(&self, account: &str) -> Result<Vec<ApiKeyDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let keys = uow.api_key().select_by_account(account).await?;
+
+        Ok(keys.into_iter().map(to_dto).collect())
+    }
+
+    pub async fn revoke(&self, account: &str, id: &str) -> Result<Option<ApiKeyDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let key = uow
+            .api_key()
+            .select_by_account(account)
+            .await?
+            .into_iter()
+            .find(|k| k.id == id);
+        if key.is_some() {
+            uow.api_key().delete(account, id).await?;
+        }
+        uow.commit().await?;
+
+        Ok(key.map(to_dto))
+    }
+
+    pub async fn authenticate(&self, key: &str) -> Result<AuthenticatedMember, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let entity = match uow.api_key().select_by_hash(&hash_token(key)).await? {
+            Some(k) if k.expires_at > now => k,
+            _ => return Err(UseCaseError::Unauthorized),
+        };
+        match uow.member().select(&entity.account).await? {
+            Some(m) if !m.disabled => {}
+            _ => return Err(UseCaseError::Unauthorized),
+        }
+        let roles = uow.member().select_roles(&entity.account).await?;
+
+        uow.api_key()
+            .touch(
+                &entity.id,
+                now,
+                now - Duration::seconds(TOUCH_INTERVAL_SECS),
+            )
+            .await?;
+        uow.commit().await?;
+
+        Ok(AuthenticatedMember {
+            scopes: Some(entity.scope_list()),
+            account: entity.account,
+            jti: entity.id,
+            expires_at: entity.expires_at.timestamp(),
+            roles,
+        })
+    }
+}
+
+fn to_dto(entity: ApiKeyEntity) -> ApiKeyDto {
+    ApiKeyDto {
+        scopes: entity.scope_list(),
+        id: entity.id,
+        name: entity.name,
+        created_at: entity.created_at,
+        expires_at: entity.expires_at,
+        last_used_at: entity.last_used_at,
+    }
+}
@@ -1,16 +1,79 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use simple_jwt::{Algorithm, JwkSet, KeyRing, SigningKey};
+use std::net::IpAddr;
 use std::sync::Arc;
+use uuid::Uuid;
+use validator::{ValidationError, ValidationErrors};
 
+use crate::cache::TtlCache;
 use crate::errors::UseCaseError;
-use crate::model::auth::{SigninRequest, SigninResponse, SignupRequest, SignupResponse};
-use domain::{UnitOfWorkProvider, model::member::MemberEntity};
+use crate::model::auth::{
+    AuthenticatedMember, ChangePasswordRequest, DeleteAccountRequest, ForgotPasswordRequest,
+    RefreshRequest, ResendVerificationRequest, ResetPasswordRequest, SigninRequest, SigninResponse,
+    SigninResult, SignoutRequest, SignupRequest, SignupResponse, UpdateTimezoneRequest,
+    VerifyEmailRequest,
+};
+use crate::model::two_factor::{TotpSigninRequest, TwoFactorChallengeResponse};
+use crate::password_policy;
+use crate::usecase::{throttle, two_factor};
+use domain::{
+    UnitOfWork, UnitOfWorkProvider,
+    interface::notifier::Notifier,
+    model::{
+        member::{MemberEntity, Role},
+        notification::Notification,
+        password_reset::PasswordResetEntity,
+        refresh_token::RefreshTokenEntity,
+        revoked_token::RevokedTokenEntity,
+        two_factor::SigninChallengeEntity,
+    },
+};
+
+const SESSION_CACHE_CAPACITY: usize = 10_000;
+const CHALLENGE_MAX_ATTEMPTS: i64 = 5;
+const VERIFICATION_AUDIENCE: &str = "email-verification";
+
+// Carries the address being verified, so a token stops working once the address changes.
+#[derive(Serialize, Deserialize)]
+struct VerificationClaims {
+    iss: String,
+    sub: String,
+    aud: String,
+    email: String,
+    iat: i64,
+    exp: i64,
+}
 
 pub struct AuthUseCase {
     provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+    sessions: TtlCache<String, String>,
+    keys: KeyRing,
+    notifier: Arc<dyn Notifier>,
 }
 
 impl AuthUseCase {
-    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>) -> Self {
-        Self { provider }
+    pub fn new(
+        provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+        notifier: Arc<dyn Notifier>,
+    ) -> Result<Self, UseCaseError> {
+        let cfg = &config::CONFIG.password;
+        async_argon2::configure(
+            cfg.hash_memory_kib,
+            cfg.hash_iterations,
+            cfg.hash_parallelism,
+        )
+        .map_err(UseCaseError::Infrastructure)?;
+        password_policy::init()?;
+
+        let ttl = std::time::Duration::from_secs(config::CONFIG.jwt.cache_ttl);
+        Ok(Self {
+            provider,
+            sessions: TtlCache::new(ttl, SESSION_CACHE_CAPACITY),
+            keys: key_ring()?,
+            notifier,
+        })
     }
 
     pub async fn signup(&self, dto: SignupRequest) -> Result<SignupResponse, UseCaseError> {
@@ -21,64 +84,754 @@ impl AuthUseCase {
                 "Password confirmation does not match".to_string(),
             ));
         }
-        if uow.member().select(&dto.account).await?.is_some() {
-            return Err(UseCaseError::AccountIdExists);
+        let require_verification = config::CONFIG.signup.require_verification;
+        if require_verification && dto.email.is_none() {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "email",
+                ValidationError::new("required").with_message("is required".into()),
+            );
+            return Err(UseCaseError::Validation(errors));
+        }
+        if let Some(existing) = uow.member().select(&dto.account).await? {
+            if !config::CONFIG.signup.conceal_existing {
+                return Err(UseCaseError::AccountIdExists);
+            }
+            // Answer exactly like a successful signup, after the same hashing work, and let
+            // the owner know instead of the caller. The notice is sent in the background like
+            // the verification mail of a new account, so delivery time does not tell them apart.
+            // Owners without an email address on file are not notified.
+            async_argon2::hash(dto.password).await?;
+            if let Some(email) = existing.email {
+                let notification = Notification {
+                    recipient: email,
+                    subject: "Signup attempt".to_string(),
+                    body: "Someone tried to sign up with your account name. \
+                           If this was you, sign in or reset your password instead."
+                        .to_string(),
+                };
+                self.deliver(notification, "signup attempt notice");
+            }
+            return Ok(SignupResponse {
+                account: dto.account,
+            });
         }
 
         let hash_password = async_argon2::hash(dto.password).await?;
         let entity = MemberEntity {
             account: dto.account.clone(),
             password: hash_password,
+            disabled: false,
+            email: dto.email,
+            verified: !require_verification,
+            verification_sent_at: None,
+            timezone: None,
         };
 
         let entity = uow.member().insert(&entity).await?;
+        uow.member()
+            .insert_role(&entity.account, Role::Member)
+            .await?;
+        if require_verification {
+            let now = Utc::now();
+            uow.member()
+                .update_verification_sent(&entity.account, now, now)
+                .await?;
+        }
         uow.commit().await?;
 
+        if let Some(email) = entity.email.as_deref()
+            && require_verification
+        {
+            self.send_verification(&entity.account, email);
+        }
+
         Ok(SignupResponse {
             account: entity.account,
         })
     }
 
-    pub async fn signin(&self, dto: SigninRequest) -> Result<SigninResponse, UseCaseError> {
+    pub async fn signin(
+        &self,
+        dto: SigninRequest,
+        ip: Option<IpAddr>,
+    ) -> Result<SigninResult, UseCaseError> {
         let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        throttle::check(uow.as_mut(), &dto.account, ip, now).await?;
 
         let member = uow.member().select(&dto.account).await?;
+        let verified = verify_password(member.as_ref(), &dto.password).await?;
         let member = match member {
-            Some(u) => u,
+            Some(m) if verified => m,
+            _ => {
+                throttle::record_failure(uow.as_mut(), &dto.account, ip, now).await?;
+                uow.commit().await?;
+                return Err(UseCaseError::Unauthorized);
+            }
+        };
+        if member.disabled {
+            return Err(UseCaseError::AccountDisabled);
+        }
+        if !member.verified {
+            return Err(UseCaseError::AccountNotVerified);
+        }
+        // Hashes made with older cost parameters are upgraded while the plain password is at hand.
+        if async_argon2::needs_rehash(&member.password) {
+            let hash_password = async_argon2::hash(dto.password).await?;
+            uow.member()
+                .update_password(&member.account, &hash_password)
+                .await?;
+        }
+
+        // The failure counter is only cleared once tokens are issued, so a known password
+        // cannot be used to reset it between second-factor guesses.
+        if two_factor::is_enabled(uow.as_mut(), &member.account).await? {
+            let expires_in = config::CONFIG.totp.challenge_expire;
+            let challenge_token = new_token();
+            uow.two_factor().delete_expired_challenges(now).await?;
+            let entity = SigninChallengeEntity {
+                token_hash: hash_token(&challenge_token),
+                account: member.account,
+                expires_at: now + Duration::seconds(expires_in),
+                attempts: 0,
+            };
+            uow.two_factor().insert_challenge(&entity).await?;
+            uow.commit().await?;
+
+            return Ok(SigninResult::Challenge(TwoFactorChallengeResponse {
+                challenge_token,
+                expires_in,
+            }));
+        }
+
+        throttle::clear(uow.as_mut(), &member.account).await?;
+        let family = Uuid::new_v4().to_string();
+        let res = issue_tokens(uow.as_mut(), &self.keys, &member.account, &family).await?;
+        uow.commit().await?;
+
+        Ok(SigninResult::Tokens(res))
+    }
+
+    pub async fn signin_totp(
+        &self,
+        dto: TotpSigninRequest,
+        ip: Option<IpAddr>,
+    ) -> Result<SigninResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let token_hash = hash_token(&dto.challenge_token);
+        let challenge = match uow.two_factor().select_challenge(&token_hash).await? {
+            Some(c) if c.expires_at > now => c,
+            _ => return Err(UseCaseError::Unauthorized),
+        };
+        throttle::check(uow.as_mut(), &challenge.account, ip, now).await?;
+
+        // Failed attempts are committed so the challenge cannot be brute-forced.
+        if !two_factor::verify_code(uow.as_mut(), &challenge.account, &dto.code).await? {
+            if challenge.attempts + 1 >= CHALLENGE_MAX_ATTEMPTS {
+                uow.two_factor().delete_challenge(&token_hash).await?;
+            } else {
+                uow.two_factor()
+                    .increment_challenge_attempts(&token_hash)
+                    .await?;
+            }
+            throttle::record_failure(uow.as_mut(), &challenge.account, ip, now).await?;
+            uow.commit().await?;
+            return Err(UseCaseError::Unauthorized);
+        }
+        if !uow.two_factor().delete_challenge(&token_hash).await? {
+            return Err(UseCaseError::Unauthorized);
+        }
+
+        match uow.member().select(&challenge.account).await? {
+            Some(m) if !m.disabled => {}
+            _ => return Err(UseCaseError::Unauthorized),
+        }
+
+        throttle::clear(uow.as_mut(), &challenge.account).await?;
+        let family = Uuid::new_v4().to_string();
+        let res = issue_tokens(uow.as_mut(), &self.keys, &challenge.account, &family).await?;
+        uow.commit().await?;
+
+        Ok(res)
+    }
+
+    pub async fn refresh(&self, dto: RefreshRequest) -> Result<SigninResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let token_hash = hash_token(&dto.refresh_token);
+        let current = match uow.refresh_token().select(&token_hash).await? {
+            Some(t) => t,
             None => return Err(UseCaseError::Unauthorized),
         };
+        if current.revoked || current.expires_at <= Utc::now() {
+            return Err(UseCaseError::Unauthorized);
+        }
 
-        if !async_argon2::verify(dto.password, member.password).await? {
+        // A refresh token that was already rotated is being replayed: assume it leaked
+        // and revoke every token descended from the same signin.
+        if current.used || !uow.refresh_token().mark_used(&token_hash).await? {
+            uow.refresh_token().revoke_family(&current.family).await?;
+            uow.commit().await?;
             return Err(UseCaseError::Unauthorized);
         }
 
-        let claims = simple_jwt::Claims::new(
-            &dto.account,
-            &config::CONFIG.jwt.issuer,
-            config::CONFIG.jwt.expire,
-        );
-        let token = simple_jwt::encode(&claims, &config::CONFIG.jwt.secret)
-            .map_err(|e| UseCaseError::Infrastructure(Box::new(e)))?;
+        match uow.member().select(&current.account).await? {
+            Some(m) if !m.disabled => {}
+            _ => return Err(UseCaseError::Unauthorized),
+        }
+
+        let res = issue_tokens(uow.as_mut(), &self.keys, &current.account, &current.family).await?;
+        uow.commit().await?;
+
+        Ok(res)
+    }
+
+    pub async fn signout(
+        &self,
+        member: &AuthenticatedMember,
+        dto: Option<SignoutRequest>,
+    ) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        uow.revoked_token().delete_expired(Utc::now()).await?;
+        let entity = RevokedTokenEntity {
+            jti: member.jti.clone(),
+            account: member.account.clone(),
+            expires_at: DateTime::from_timestamp(member.expires_at, 0).unwrap_or_else(Utc::now),
+        };
+        uow.revoked_token().insert(&entity).await?;
+
+        if let Some(refresh_token) = dto.and_then(|d| d.refresh_token) {
+            let token_hash = hash_token(&refresh_token);
+            let current = uow.refresh_token().select(&token_hash).await?;
+            if let Some(t) = current
+                && t.account == member.account
+            {
+                uow.refresh_token().revoke_family(&t.family).await?;
+            }
+        }
+
+        uow.commit().await?;
+        self.sessions.remove(&member.jti);
+
+        Ok(())
+    }
+
+    pub async fn signout_all(&self, account: &str) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        revoke_sessions(uow.as_mut(), account, Utc::now()).await?;
+        uow.commit().await?;
+        self.forget_account(account);
+
+        Ok(())
+    }
+
+    // Every existing session is revoked; the caller continues with the token pair returned here.
+    // Throttled like signin, so a stolen session cannot be used to guess the password.
+    pub async fn change_password(
+        &self,
+        account: &str,
+        dto: ChangePasswordRequest,
+        ip: Option<IpAddr>,
+    ) -> Result<SigninResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        throttle::check(uow.as_mut(), account, ip, now).await?;
+        let member = match uow.member().select(account).await? {
+            Some(m) if !m.disabled => m,
+            _ => return Err(UseCaseError::Unauthorized),
+        };
+        if !async_argon2::verify(dto.current_password, member.password).await? {
+            throttle::record_failure(uow.as_mut(), account, ip, now).await?;
+            uow.commit().await?;
+            return Err(UseCaseError::InvalidCredentials);
+        }
+        throttle::clear(uow.as_mut(), account).await?;
+
+        let hash_password = async_argon2::hash(dto.new_password).await?;
+        uow.member()
+            .update_password(account, &hash_password)
+            .await?;
+        uow.password_reset().delete_by_account(account).await?;
+        revoke_sessions(uow.as_mut(), account, now).await?;
+
+        let family = Uuid::new_v4().to_string();
+        let res = issue_tokens(uow.as_mut(), &self.keys, account, &family).await?;
+        uow.commit().await?;
+        self.forget_account(account);
+
+        Ok(res)
+    }
+
+    pub async fn update_timezone(
+        &self,
+        account: &str,
+        dto: UpdateTimezoneRequest,
+    ) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        if !uow
+            .member()
+            .update_timezone(account, dto.timezone.as_deref())
+            .await?
+        {
+            return Err(UseCaseError::Unauthorized);
+        }
+        uow.commit().await?;
+
+        Ok(())
+    }
+
+    // Always succeeds so the response does not reveal whether the account exists.
+    pub async fn forgot_password(&self, dto: ForgotPasswordRequest) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let recipient = match uow.member().select(&dto.account).await? {
+            Some(m) if !m.disabled => m.email.unwrap_or(m.account),
+            _ => return Ok(()),
+        };
+
+        let expires_in = config::CONFIG.password.reset_expire;
+        let token = new_token();
+        uow.password_reset().delete_expired(now).await?;
+        uow.password_reset().delete_by_account(&dto.account).await?;
+        let entity = PasswordResetEntity {
+            token_hash: hash_token(&token),
+            account: dto.account.clone(),
+            expires_at: now + Duration::seconds(expires_in),
+        };
+        uow.password_reset().insert(&entity).await?;
+        uow.commit().await?;
+
+        let notification = Notification {
+            recipient,
+            subject: "Password reset".to_string(),
+            body: format!(
+                "A password reset was requested for your account.\n\
+                 Reset token: {token}\n\
+                 The token expires in {expires_in} seconds. If you did not request this, ignore this message."
+            ),
+        };
+        self.deliver(notification, "password reset token");
+
+        Ok(())
+    }
+
+    pub async fn reset_password(&self, dto: ResetPasswordRequest) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let reset = match uow.password_reset().select(&hash_token(&dto.token)).await? {
+            Some(r) if r.expires_at > now => r,
+            _ => {
+                return Err(UseCaseError::BadRequest(
+                    "Invalid or expired reset token".to_string(),
+                ));
+            }
+        };
+        match uow.member().select(&reset.account).await? {
+            Some(m) if !m.disabled => {}
+            _ => {
+                return Err(UseCaseError::BadRequest(
+                    "Invalid or expired reset token".to_string(),
+                ));
+            }
+        }
+
+        let hash_password = async_argon2::hash(dto.password).await?;
+        uow.member()
+            .update_password(&reset.account, &hash_password)
+            .await?;
+        uow.password_reset()
+            .delete_by_account(&reset.account)
+            .await?;
+        revoke_sessions(uow.as_mut(), &reset.account, now).await?;
+        throttle::clear(uow.as_mut(), &reset.account).await?;
+        uow.commit().await?;
+        self.forget_account(&reset.account);
+
+        Ok(())
+    }
+
+    pub async fn verify_email(&self, dto: VerifyEmailRequest) -> Result<(), UseCaseError> {
+        let invalid =
+            || UseCaseError::BadRequest("Invalid or expired verification token".to_string());
+        let claims: VerificationClaims = self
+            .verify(
+                &dto.token,
+                &config::CONFIG.jwt.issuer,
+                VERIFICATION_AUDIENCE,
+            )
+            .map_err(|_| invalid())?;
+
+        let mut uow = self.provider.begin().await?;
+        if !uow
+            .member()
+            .update_verified(&claims.sub, &claims.email)
+            .await?
+        {
+            return Err(invalid());
+        }
+        uow.commit().await?;
+
+        Ok(())
+    }
+
+    // Always succeeds so the response does not reveal whether the account exists; nothing is
+    // sent within `signup.resend_interval` of the previous mail.
+    pub async fn resend_verification(
+        &self,
+        dto: ResendVerificationRequest,
+    ) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let email = match uow.member().select(&dto.account).await? {
+            Some(m) if !m.disabled && !m.verified => match m.email {
+                Some(email) => email,
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let since = now - Duration::seconds(config::CONFIG.signup.resend_interval);
+        if !uow
+            .member()
+            .update_verification_sent(&dto.account, now, since)
+            .await?
+        {
+            return Ok(());
+        }
+        uow.commit().await?;
+
+        self.send_verification(&dto.account, &email);
+        Ok(())
+    }
+
+    pub async fn delete_account(
+        &self,
+        account: &str,
+        dto: DeleteAccountRequest,
+        ip: Option<IpAddr>,
+    ) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        throttle::check(uow.as_mut(), account, ip, now).await?;
+        let member = match uow.member().select(account).await? {
+            Some(m) => m,
+            None => return Err(UseCaseError::Unauthorized),
+        };
+        let mut verified = async_argon2::verify(dto.password, member.password).await?;
+        if verified && two_factor::is_enabled(uow.as_mut(), account).await? {
+            verified = match &dto.code {
+                Some(code) => two_factor::verify_code(uow.as_mut(), account, code).await?,
+                None => false,
+            };
+        }
+        if !verified {
+            throttle::record_failure(uow.as_mut(), account, ip, now).await?;
+            uow.commit().await?;
+            return Err(UseCaseError::InvalidCredentials);
+        }
+        throttle::clear(uow.as_mut(), account).await?;
+
+        delete_member(uow.as_mut(), account).await?;
+        uow.commit().await?;
+        self.forget_account(account);
+
+        Ok(())
+    }
+
+    // Delivery failures are logged; the member can ask for another mail.
+    fn send_verification(&self, account: &str, email: &str) {
+        let cfg = &config::CONFIG.signup;
+        let now = Utc::now();
+        let claims = VerificationClaims {
+            iss: config::CONFIG.jwt.issuer.clone(),
+            sub: account.to_string(),
+            aud: VERIFICATION_AUDIENCE.to_string(),
+            email: email.to_string(),
+            iat: now.timestamp(),
+            exp: (now + Duration::seconds(cfg.verification_expire)).timestamp(),
+        };
+        let token = match self.sign(&claims) {
+            Ok(token) => token,
+            Err(e) => {
+                tracing::error!("failed to sign the verification token: {e}");
+                return;
+            }
+        };
+        let link = match &cfg.verify_url {
+            Some(url) => format!("Open {url}?token={token} to verify it.\n"),
+            None => String::new(),
+        };
+        let notification = Notification {
+            recipient: email.to_string(),
+            subject: "Verify your email address".to_string(),
+            body: format!(
+                "Confirm that this address belongs to the account {account}.\n\
+                 {link}\
+                 Verification token: {token}\n\
+                 The token expires in {} seconds. If you did not sign up, ignore this message.",
+                cfg.verification_expire
+            ),
+        };
+        self.deliver(notification, "verification mail");
+    }
+
+    // Runs off the request path, so the time a mail server takes never shows in the response
+    // time of the endpoints that must not reveal whether an account exists.
+    fn deliver(&self, notification: Notification, what: &'static str) {
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            if let Err(e) = notifier.notify(&notification).await {
+                tracing::error!("failed to deliver the {what}: {e}");
+            }
+        });
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        self.keys.jwks(Utc::now())
+    }
+
+    pub(crate) fn signing_algorithm(&self) -> Option<Algorithm> {
+        self.keys.active(Utc::now()).map(|k| k.algorithm())
+    }
 
-        Ok(SigninResponse { token })
+    // Signs tokens for other audiences (OpenID Connect) with the same keys as sessions.
+    pub(crate) fn sign<T: Serialize>(&self, claims: &T) -> Result<String, UseCaseError> {
+        simple_jwt::encode(claims, &self.keys)
+            .map_err(|e| UseCaseError::Infrastructure(Box::new(e)))
     }
 
-    pub async fn authenticate(&self, token: &str) -> Result<String, UseCaseError> {
-        let claims = match simple_jwt::decode(
-            token,
-            &config::CONFIG.jwt.issuer,
-            &config::CONFIG.jwt.secret,
-        ) {
+    pub(crate) fn verify<T: DeserializeOwned>(
+        &self,
+        token: &str,
+        iss: &str,
+        aud: &str,
+    ) -> Result<T, UseCaseError> {
+        simple_jwt::decode_for(token, iss, aud, &self.keys).map_err(|_| UseCaseError::Unauthorized)
+    }
+
+    pub(crate) fn forget_account(&self, account: &str) {
+        self.sessions.retain(|_, a| a != account);
+    }
+
+    pub async fn authenticate(&self, token: &str) -> Result<AuthenticatedMember, UseCaseError> {
+        let claims = match simple_jwt::decode(token, &config::CONFIG.jwt.issuer, &self.keys) {
             Ok(c) => c,
             Err(_) => return Err(UseCaseError::Unauthorized),
         };
 
+        let roles = claims.roles.iter().filter_map(|r| r.parse().ok()).collect();
+
+        if let Some(account) = self.sessions.get(&claims.jti) {
+            return Ok(AuthenticatedMember {
+                account,
+                jti: claims.jti,
+                expires_at: claims.exp,
+                roles,
+                scopes: None,
+            });
+        }
+
         let mut uow = self.provider.begin().await?;
 
+        if uow.revoked_token().exists(&claims.jti).await? {
+            return Err(UseCaseError::Unauthorized);
+        }
+        if let Some(revoked_at) = uow
+            .revoked_token()
+            .select_account_revoked_at(&claims.sub)
+            .await?
+            && issued_by(claims.iat, claims.iat_us, revoked_at)
+        {
+            return Err(UseCaseError::Unauthorized);
+        }
+
         let member = match uow.member().select(&claims.sub).await? {
-            Some(u) => u,
-            None => return Err(UseCaseError::Unauthorized),
+            Some(u) if !u.disabled => u,
+            _ => return Err(UseCaseError::Unauthorized),
         };
-        Ok(member.account.clone())
+
+        self.sessions
+            .insert(claims.jti.clone(), member.account.clone());
+
+        Ok(AuthenticatedMember {
+            account: member.account,
+            jti: claims.jti,
+            expires_at: claims.exp,
+            roles,
+            scopes: None,
+        })
+    }
+}
+
+// Removes the member together with their todos, tags and every credential tied to the account.
+pub(crate) async fn delete_member(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+) -> Result<(), UseCaseError> {
+    uow.checklist().delete_by_account(account).await?;
+    uow.todo().delete_by_account(account).await?;
+    uow.tag().delete_by_account(account).await?;
+    revoke_sessions(uow, account, Utc::now()).await?;
+    uow.api_key().delete_by_account(account).await?;
+    uow.oidc().delete_codes_by_account(account).await?;
+    uow.password_reset().delete_by_account(account).await?;
+    two_factor::delete_all(uow, account).await?;
+    throttle::clear(uow, account).await?;
+    uow.member().delete_roles(account).await?;
+    uow.member().delete(account).await?;
+    Ok(())
+}
+
+// Runs exactly one argon2 verification whether or not the account exists, so the response
+// time does not reveal which accounts are registered.
+async fn verify_password(
+    member: Option<&MemberEntity>,
+    password: &str,
+) -> Result<bool, UseCaseError> {
+    match member {
+        Some(m) => Ok(async_argon2::verify(password.to_string(), m.password.clone()).await?),
+        None => {
+            async_argon2::verify_dummy(password.to_string()).await?;
+            Ok(false)
+        }
+    }
+}
+
+// Whether a token was issued no later than `at`. `iat` only has whole seconds, so the
+// microsecond claim decides between tokens issued in the same second as a revocation;
+// tokens without it count from the start of their second.
+pub(crate) fn issued_by(iat: i64, iat_us: Option<i64>, at: DateTime<Utc>) -> bool {
+    iat_us.unwrap_or(iat.saturating_mul(1_000_000)) <= at.timestamp_micros()
+}
+
+async fn revoke_sessions(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    at: DateTime<Utc>,
+) -> Result<(), UseCaseError> {
+    uow.refresh_token().revoke_account(account).await?;
+    uow.revoked_token().revoke_account(account, at).await?;
+    Ok(())
+}
+
+async fn issue_tokens(
+    uow: &mut dyn UnitOfWork,
+    keys: &KeyRing,
+    account: &str,
+    family: &str,
+) -> Result<SigninResponse, UseCaseError> {
+    let roles = uow.member().select_roles(account).await?;
+    let claims = simple_jwt::Claims::new(
+        account,
+        &config::CONFIG.jwt.issuer,
+        config::CONFIG.jwt.expire,
+    )
+    .with_roles(roles.iter().map(|r| r.to_string()).collect());
+    let token =
+        simple_jwt::encode(&claims, keys).map_err(|e| UseCaseError::Infrastructure(Box::new(e)))?;
+
+    let refresh_token = new_token();
+    let entity = RefreshTokenEntity {
+        token_hash: hash_token(&refresh_token),
+        family: family.to_string(),
+        account: account.to_string(),
+        expires_at: Utc::now() + Duration::seconds(config::CONFIG.jwt.refresh_expire),
+        used: false,
+        revoked: false,
+    };
+    uow.refresh_token().insert(&entity).await?;
+
+    Ok(SigninResponse {
+        token,
+        refresh_token,
+    })
+}
+
+// Without `jwt.keys` the ring holds the single key configured directly under `jwt`.
+fn key_ring() -> Result<KeyRing, UseCaseError> {
+    let cfg = &config::CONFIG.jwt;
+    if cfg.keys.is_empty() {
+        let secret = cfg.secret.clone().unwrap_or_else(|| {
+            tracing::warn!(
+                "jwt.secret is not configured; tokens are signed with a random secret and will not survive a restart"
+            );
+            Uuid::new_v4().to_string()
+        });
+        let key = signing_key(
+            &cfg.algorithm,
+            Some(&secret),
+            cfg.private_key.as_deref(),
+            cfg.kid.clone(),
+        )?;
+        return Ok(key.into());
     }
+
+    let mut ring = KeyRing::new();
+    for k in &cfg.keys {
+        let key = signing_key(
+            &k.algorithm,
+            k.secret.as_deref(),
+            k.private_key.as_deref(),
+            Some(k.kid.clone()),
+        )?;
+        if !ring.insert(key, k.activate_at, k.retire_at) {
+            return Err(UseCaseError::Infrastructure(
+                format!("duplicate JWT kid '{}'", k.kid).into(),
+            ));
+        }
+    }
+    if ring.active(Utc::now()).is_none() {
+        return Err(UseCaseError::Infrastructure(
+            "no JWT key in jwt.keys is active".into(),
+        ));
+    }
+    Ok(ring)
+}
+
+fn signing_key(
+    algorithm: &str,
+    secret: Option<&str>,
+    private_key: Option<&str>,
+    kid: Option<String>,
+) -> Result<SigningKey, UseCaseError> {
+    let alg: Algorithm = algorithm.parse().map_err(|_| {
+        UseCaseError::Infrastructure(format!("unsupported JWT algorithm '{algorithm}'").into())
+    })?;
+    if matches!(alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+        let secret = secret.ok_or_else(|| {
+            UseCaseError::Infrastructure(format!("a JWT secret is required for {algorithm}").into())
+        })?;
+        return SigningKey::from_secret(alg, secret, kid)
+            .map_err(|e| UseCaseError::Infrastructure(Box::new(e)));
+    }
+
+    let path = private_key.ok_or_else(|| {
+        UseCaseError::Infrastructure(
+            format!("a JWT private_key is required for {algorithm}").into(),
+        )
+    })?;
+    let pem = std::fs::read(path).map_err(|e| {
+        UseCaseError::Infrastructure(format!("failed to read JWT key '{path}': {e}").into())
+    })?;
+    SigningKey::from_pem(alg, &pem, kid)
+        .map_err(|e| UseCaseError::Infrastructure(format!("invalid JWT key '{path}': {e}").into()))
+}
+
+pub(crate) fn new_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+pub(crate) fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
 }
@@ -0,0 +1,525 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use simple_jwt::Algorithm;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::UseCaseError;
+use crate::model::oidc::{
+    AuthorizeRequest, AuthorizeResponse, CreateOidcClientRequest, CreateOidcClientResponse,
+    OidcClientDto, OpenIdConfiguration, TokenRequest, TokenResponse, UserInfoResponse,
+};
+use crate::usecase::auth::{AuthUseCase, hash_token, issued_by, new_token};
+use domain::{
+    UnitOfWork, UnitOfWorkProvider,
+    model::oidc::{AuthorizationCodeEntity, OidcClientEntity},
+};
+
+const SCOPE_OPENID: &str = "openid";
+const SCOPE_PROFILE: &str = "profile";
+const SUPPORTED_SCOPES: [&str; 2] = [SCOPE_OPENID, SCOPE_PROFILE];
+
+#[derive(Serialize, Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: String,
+    iat: i64,
+    exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preferred_username: Option<String>,
+}
+
+// RFC 9068: the audience is this provider's userinfo resource, the client is named apart,
+// so ID tokens (audience: the client) are never accepted as access tokens.
+#[derive(Serialize, Deserialize)]
+struct AccessTokenClaims {
+    iss: String,
+    sub: String,
+    aud: String,
+    client_id: String,
+    scope: String,
+    iat: i64,
+    exp: i64,
+    jti: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iat_us: Option<i64>,
+}
+
+// An authorization request whose client and redirect URI have been checked, so any
+// further error can be reported to the client through the redirect.
+struct ClientRedirect {
+    client_id: String,
+    redirect_uri: String,
+    state: Option<String>,
+}
+
+pub struct OidcUseCase {
+    provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+    auth: Arc<AuthUseCase>,
+    issuer: Option<String>,
+}
+
+impl OidcUseCase {
+    pub fn new(
+        provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+        auth: Arc<AuthUseCase>,
+    ) -> Result<Self, UseCaseError> {
+        let issuer = config::CONFIG
+            .oidc
+            .issuer
+            .as_ref()
+            .map(|i| i.trim_end_matches('/').to_string());
+        if let Some(issuer) = &issuer {
+            if !issuer.starts_with("https://") && !issuer.starts_with("http://") {
+                return Err(UseCaseError::Infrastructure(
+                    format!("oidc.issuer must be an http(s) URL: '{issuer}'").into(),
+                ));
+            }
+            if auth.signing_algorithm().is_some_and(|alg| {
+                matches!(alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512)
+            }) {
+                tracing::warn!(
+                    "ID tokens are signed with an HMAC key that clients cannot verify; configure an asymmetric jwt.algorithm"
+                );
+            }
+        }
+        Ok(Self {
+            provider,
+            auth,
+            issuer,
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.issuer.is_some()
+    }
+
+    pub fn discovery(&self) -> Result<OpenIdConfiguration, UseCaseError> {
+        let issuer = self.issuer()?;
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+        Ok(OpenIdConfiguration {
+            authorization_endpoint: format!("{issuer}/service/oauth2/authorize"),
+            token_endpoint: format!("{issuer}/service/oauth2/token"),
+            userinfo_endpoint: format!("{issuer}/service/oauth2/userinfo"),
+            jwks_uri: format!("{issuer}/.well-known/jwks.json"),
+            issuer: issuer.to_string(),
+            response_types_supported: strings(&["code"]),
+            grant_types_supported: strings(&["authorization_code"]),
+            subject_types_supported: strings(&["public"]),
+            id_token_signing_alg_values_supported: self
+                .auth
+                .signing_algorithm()
+                .map(|alg| vec![format!("{alg:?}")])
+                .unwrap_or_default(),
+            scopes_supported: strings(&SUPPORTED_SCOPES),
+            claims_supported: strings(&[
+                "iss",
+                "sub",
+                "aud",
+                "iat",
+                "exp",
+                "nonce",
+                "preferred_username",
+            ]),
+            token_endpoint_auth_methods_supported: strings(&[
+                "client_secret_basic",
+                "client_secret_post",
+                "none",
+            ]),
+            code_challenge_methods_supported: strings(&["S256"]),
+        })
+    }
+
+    pub async fn create_client(
+        &self,
+        dto: CreateOidcClientRequest,
+    ) -> Result<CreateOidcClientResponse, UseCaseError> {
+        let mut redirect_uris = dto.redirect_uris;
+        redirect_uris.sort();
+        redirect_uris.dedup();
+        if redirect_uris.is_empty() {
+            return Err(UseCaseError::BadRequest(
+                "at least one redirect URI is required".into(),
+            ));
+        }
+
+        let mut uow = self.provider.begin().await?;
+
+        let client_secret = (!dto.public).then(new_token);
+        let entity = OidcClientEntity {
+            client_id: Uuid::new_v4().to_string(),
+            name: dto.name.trim().to_string(),
+            secret_hash: client_secret.as_deref().map(hash_token),
+            redirect_uris: redirect_uris.join(" "),
+            created_at: Utc::now(),
+        };
+        uow.oidc().insert_client(&entity).await?;
+        uow.commit().await?;
+
+        Ok(CreateOidcClientResponse {
+            client_secret,
+            client: to_dto(entity),
+        })
+    }
+
+    pub async fn list_clients(&self) -> Result<Vec<OidcClientDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let clients = uow.oidc().select_clients().await?;
+
+        Ok(clients.into_iter().map(to_dto).collect())
+    }
+
+    // Tokens already issued to the client stop working at userinfo.
+    pub async fn delete_client(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<OidcClientDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let client = uow.oidc().select_client(client_id).await?;
+        if client.is_some() {
+            uow.oidc().delete_codes_by_client(client_id).await?;
+            uow.oidc().delete_client(client_id).await?;
+        }
+        uow.commit().await?;
+
+        Ok(client.map(to_dto))
+    }
+
+    // Entry point for the browser: sends it on to the login page with the request intact,
+    // which signs the member in and then calls `authorize`.
+    pub async fn login_redirect(
+        &self,
+        req: &AuthorizeRequest,
+        query: &str,
+    ) -> Result<String, UseCaseError> {
+        self.issuer()?;
+        let mut uow = self.provider.begin().await?;
+
+        let target = client_redirect(uow.as_mut(), req).await?;
+        if let Err((error, description)) = check_request(req) {
+            return Ok(error_redirect(&target, error, description));
+        }
+        match &config::CONFIG.oidc.login_url {
+            Some(login_url) => Ok(append_query(login_url, query)),
+            None => Ok(error_redirect(
+                &target,
+                "login_required",
+                "No login page is configured",
+            )),
+        }
+    }
+
+    // Consent is implied: the provider only serves clients registered by an administrator.
+    pub async fn authorize(
+        &self,
+        account: &str,
+        req: AuthorizeRequest,
+    ) -> Result<AuthorizeResponse, UseCaseError> {
+        self.issuer()?;
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let target = client_redirect(uow.as_mut(), &req).await?;
+        let scope = match check_request(&req) {
+            Ok(scope) => scope,
+            Err((error, description)) => {
+                return Ok(AuthorizeResponse {
+                    redirect_to: error_redirect(&target, error, description),
+                });
+            }
+        };
+
+        uow.oidc().delete_expired_codes(now).await?;
+        let code = new_token();
+        let entity = AuthorizationCodeEntity {
+            code_hash: hash_token(&code),
+            client_id: target.client_id.clone(),
+            account: account.to_string(),
+            redirect_uri: target.redirect_uri.clone(),
+            scope,
+            nonce: req.nonce,
+            code_challenge: req.code_challenge.unwrap_or_default(),
+            expires_at: now + Duration::seconds(config::CONFIG.oidc.code_expire),
+        };
+        uow.oidc().insert_code(&entity).await?;
+        uow.commit().await?;
+
+        let mut params = vec![("code", code.as_str())];
+        if let Some(state) = &target.state {
+            params.push(("state", state));
+        }
+        Ok(AuthorizeResponse {
+            redirect_to: append_query(&target.redirect_uri, &encode_query(&params)),
+        })
+    }
+
+    // `basic` holds the client id and secret from an `Authorization: Basic` header.
+    pub async fn token(
+        &self,
+        basic: Option<(String, String)>,
+        req: TokenRequest,
+    ) -> Result<TokenResponse, UseCaseError> {
+        let issuer = self.issuer()?;
+        if req.grant_type.as_deref() != Some("authorization_code") {
+            return Err(oauth_error(
+                "unsupported_grant_type",
+                "Only authorization_code is supported",
+            ));
+        }
+        let (client_id, client_secret) = match (basic, req.client_id) {
+            (Some(_), _) if req.client_secret.is_some() => {
+                return Err(oauth_error(
+                    "invalid_request",
+                    "Use only one client authentication method",
+                ));
+            }
+            (Some((id, secret)), _) => (id, Some(secret)),
+            (None, Some(id)) => (id, req.client_secret),
+            (None, None) => {
+                return Err(oauth_error(
+                    "invalid_client",
+                    "Client authentication failed",
+                ));
+            }
+        };
+        let (Some(code), Some(code_verifier)) = (req.code, req.code_verifier) else {
+            return Err(oauth_error(
+                "invalid_request",
+                "code and code_verifier are required",
+            ));
+        };
+
+        let mut uow = self.provider.begin().await?;
+        let now = Utc::now();
+
+        let client = match uow.oidc().select_client(&client_id).await? {
+            Some(c) if client_authenticated(&c, client_secret.as_deref()) => c,
+            _ => {
+                return Err(oauth_error(
+                    "invalid_client",
+                    "Client authentication failed",
+                ));
+            }
+        };
+        // A failed exchange still uses up the code.
+        let grant = uow.oidc().take_code(&hash_token(&code)).await?;
+        uow.commit().await?;
+
+        let grant = match grant {
+            Some(g)
+                if g.client_id == client.client_id
+                    && g.expires_at > now
+                    && req.redirect_uri.as_deref() == Some(g.redirect_uri.as_str())
+                    && pkce_challenge(&code_verifier).as_deref()
+                        == Some(g.code_challenge.as_str()) =>
+            {
+                g
+            }
+            _ => {
+                return Err(oauth_error(
+                    "invalid_grant",
+                    "The authorization code is invalid, expired or was issued to another client",
+                ));
+            }
+        };
+
+        let mut uow = self.provider.begin().await?;
+        match uow.member().select(&grant.account).await? {
+            Some(m) if !m.disabled => {}
+            _ => {
+                return Err(oauth_error(
+                    "invalid_grant",
+                    "The account is no longer available",
+                ));
+            }
+        }
+
+        let expire = config::CONFIG.oidc.token_expire;
+        let profile = grant.scope.split(' ').any(|s| s == SCOPE_PROFILE);
+        let id_token = IdTokenClaims {
+            iss: issuer.to_string(),
+            sub: grant.account.clone(),
+            aud: client.client_id.clone(),
+            iat: now.timestamp(),
+            exp: now.timestamp() + expire,
+            nonce: grant.nonce,
+            preferred_username: profile.then(|| grant.account.clone()),
+        };
+        let access_token = AccessTokenClaims {
+            iss: issuer.to_string(),
+            sub: grant.account,
+            aud: issuer.to_string(),
+            client_id: client.client_id,
+            scope: grant.scope.clone(),
+            iat: now.timestamp(),
+            exp: now.timestamp() + expire,
+            jti: Uuid::new_v4().to_string(),
+            iat_us: Some(now.timestamp_micros()),
+        };
+
+        Ok(TokenResponse {
+            access_token: self.auth.sign(&access_token)?,
+            token_type: "Bearer".to_string(),
+            expires_in: expire,
+            id_token: self.auth.sign(&id_token)?,
+            scope: grant.scope,
+        })
+    }
+
+    pub async fn userinfo(&self, token: &str) -> Result<UserInfoResponse, UseCaseError> {
+        let issuer = self.issuer()?;
+        let claims: AccessTokenClaims = self.auth.verify(token, issuer, issuer)?;
+
+        let mut uow = self.provider.begin().await?;
+
+        if uow.oidc().select_client(&claims.client_id).await?.is_none() {
+            return Err(UseCaseError::Unauthorized);
+        }
+        match uow.member().select(&claims.sub).await? {
+            Some(m) if !m.disabled => {}
+            _ => return Err(UseCaseError::Unauthorized),
+        }
+        if let Some(revoked_at) = uow
+            .revoked_token()
+            .select_account_revoked_at(&claims.sub)
+            .await?
+            && issued_by(claims.iat, claims.iat_us, revoked_at)
+        {
+            return Err(UseCaseError::Unauthorized);
+        }
+
+        let profile = claims.scope.split(' ').any(|s| s == SCOPE_PROFILE);
+        Ok(UserInfoResponse {
+            preferred_username: profile.then(|| claims.sub.clone()),
+            sub: claims.sub,
+        })
+    }
+
+    fn issuer(&self) -> Result<&str, UseCaseError> {
+        self.issuer.as_deref().ok_or(UseCaseError::Forbidden)
+    }
+}
+
+// Errors here must not redirect: the redirect URI is not yet known to belong to the client.
+async fn client_redirect(
+    uow: &mut dyn UnitOfWork,
+    req: &AuthorizeRequest,
+) -> Result<ClientRedirect, UseCaseError> {
+    let client_id = req
+        .client_id
+        .as_deref()
+        .ok_or_else(|| oauth_error("invalid_request", "client_id is required"))?;
+    let client = uow
+        .oidc()
+        .select_client(client_id)
+        .await?
+        .ok_or_else(|| oauth_error("invalid_request", "Unknown client_id"))?;
+    let redirect_uri = req
+        .redirect_uri
+        .as_deref()
+        .filter(|uri| client.redirect_uri_list().iter().any(|r| r == uri))
+        .ok_or_else(|| {
+            oauth_error(
+                "invalid_request",
+                "redirect_uri is missing or not registered for the client",
+            )
+        })?;
+
+    Ok(ClientRedirect {
+        client_id: client.client_id,
+        redirect_uri: redirect_uri.to_string(),
+        state: req.state.clone(),
+    })
+}
+
+// Returns the granted scope, or the error code and description to send back to the client.
+fn check_request(req: &AuthorizeRequest) -> Result<String, (&'static str, &'static str)> {
+    if req.response_type.as_deref() != Some("code") {
+        return Err((
+            "unsupported_response_type",
+            "Only response_type=code is supported",
+        ));
+    }
+    let requested: Vec<&str> = req
+        .scope
+        .as_deref()
+        .unwrap_or_default()
+        .split(' ')
+        .collect();
+    if !requested.contains(&SCOPE_OPENID) {
+        return Err(("invalid_scope", "scope must include openid"));
+    }
+    if req.code_challenge_method.as_deref() != Some("S256") {
+        return Err((
+            "invalid_request",
+            "PKCE with code_challenge_method=S256 is required",
+        ));
+    }
+    match req.code_challenge.as_deref() {
+        Some(c) if c.len() == 43 && URL_SAFE_NO_PAD.decode(c).is_ok() => {}
+        _ => return Err(("invalid_request", "code_challenge is invalid")),
+    }
+
+    // Unknown scopes are ignored, as OpenID Connect requires.
+    Ok(SUPPORTED_SCOPES
+        .iter()
+        .filter(|s| requested.contains(s))
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+fn client_authenticated(client: &OidcClientEntity, secret: Option<&str>) -> bool {
+    match (&client.secret_hash, secret) {
+        (Some(hash), Some(secret)) => *hash == hash_token(secret),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+// RFC 7636 S256: BASE64URL(SHA256(code_verifier)); None for a malformed verifier.
+fn pkce_challenge(verifier: &str) -> Option<String> {
+    let valid = (43..=128).contains(&verifier.len())
+        && verifier
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'));
+    valid.then(|| URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())))
+}
+
+fn error_redirect(target: &ClientRedirect, error: &str, description: &str) -> String {
+    let mut params = vec![("error", error), ("error_description", description)];
+    if let Some(state) = &target.state {
+        params.push(("state", state));
+    }
+    append_query(&target.redirect_uri, &encode_query(&params))
+}
+
+fn append_query(uri: &str, query: &str) -> String {
+    let separator = if uri.contains('?') { '&' } else { '?' };
+    format!("{uri}{separator}{query}")
+}
+
+fn encode_query(params: &[(&str, &str)]) -> String {
+    serde_urlencoded::to_string(params).unwrap_or_default()
+}
+
+fn oauth_error(error: &'static str, description: &str) -> UseCaseError {
+    UseCaseError::OAuth(error, description.to_string())
+}
+
+fn to_dto(entity: OidcClientEntity) -> OidcClientDto {
+    OidcClientDto {
+        redirect_uris: entity.redirect_uri_list(),
+        public: entity.secret_hash.is_none(),
+        client_id: entity.client_id,
+        name: entity.name,
+        created_at: entity.created_at,
+    }
+}
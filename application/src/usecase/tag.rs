@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use crate::errors::UseCaseError;
+use crate::model::tag::{CreateTagRequest, TagDto, UpdateTagRequest};
+use domain::{UnitOfWork, UnitOfWorkProvider, model::tag::TagEntity};
+
+const MAX_TAGS_PER_MEMBER: usize = 100;
+
+pub struct TagUseCase {
+    provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+}
+
+impl TagUseCase {
+    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>) -> Self {
+        Self { provider }
+    }
+
+    pub async fn create(
+        &self,
+        account: &str,
+        dto: CreateTagRequest,
+    ) -> Result<TagDto, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        if uow.tag().select_by_account(account).await?.len() >= MAX_TAGS_PER_MEMBER {
+            return Err(UseCaseError::BadRequest(format!(
+                "A member can have at most {MAX_TAGS_PER_MEMBER} tags"
+            )));
+        }
+        let name = dto.name.trim().to_string();
+        ensure_unique(uow.as_mut(), account, &name, None).await?;
+
+        let entity = TagEntity {
+            id: 0,
+            account: account.to_string(),
+            name,
+        };
+        let entity = uow.tag().insert(&entity).await?;
+        uow.commit().await?;
+
+        Ok(entity.into())
+    }
+
+    pub async fn list(&self, account: &str) -> Result<Vec<TagDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let tags = uow.tag().select_by_account(account).await?;
+
+        Ok(tags.into_iter().map(TagDto::from).collect())
+    }
+
+    pub async fn update(
+        &self,
+        account: &str,
+        id: i64,
+        dto: UpdateTagRequest,
+    ) -> Result<Option<TagDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let entity = match uow.tag().select(account, id).await? {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+        let name = dto.name.trim().to_string();
+        ensure_unique(uow.as_mut(), account, &name, Some(id)).await?;
+
+        let entity = uow.tag().update(&TagEntity { name, ..entity }).await?;
+        uow.commit().await?;
+
+        Ok(entity.map(TagDto::from))
+    }
+
+    // Removes the tag from every todo it was assigned to.
+    pub async fn delete(&self, account: &str, id: i64) -> Result<Option<TagDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let entity = uow.tag().delete(account, id).await?;
+        uow.commit().await?;
+
+        Ok(entity.map(TagDto::from))
+    }
+}
+
+async fn ensure_unique(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    name: &str,
+    id: Option<i64>,
+) -> Result<(), UseCaseError> {
+    let existing = uow
+        .tag()
+        .select_by_names(account, &[name.to_string()])
+        .await?;
+    if existing.iter().any(|t| Some(t.id) != id) {
+        return Err(UseCaseError::BadRequest(format!(
+            "A tag named '{name}' already exists"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Duration, Utc};
+use std::net::IpAddr;
+
+use crate::errors::UseCaseError;
+use domain::{
+    UnitOfWork,
+    model::login_attempt::{SCOPE_ACCOUNT, SCOPE_IP},
+};
+
+// Rejects the attempt before any password hashing while the account or client IP is blocked.
+pub(crate) async fn check(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    ip: Option<IpAddr>,
+    now: DateTime<Utc>,
+) -> Result<(), UseCaseError> {
+    let mut blocked_until = None;
+    for (scope, subject) in subjects(account, ip) {
+        if let Some(attempt) = uow.login_attempt().select(scope, &subject).await?
+            && let Some(until) = attempt.blocked_until
+            && until > now
+        {
+            blocked_until = blocked_until.max(Some(until));
+        }
+    }
+
+    match blocked_until {
+        Some(until) => Err(UseCaseError::TooManyAttempts(retry_after(until, now))),
+        None => Ok(()),
+    }
+}
+
+pub(crate) async fn record_failure(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    ip: Option<IpAddr>,
+    now: DateTime<Utc>,
+) -> Result<(), UseCaseError> {
+    let cfg = &config::CONFIG.throttle;
+    let window = Duration::seconds(cfg.lockout_duration);
+    uow.login_attempt()
+        .delete_stale(now - window.max(Duration::seconds(cfg.backoff_max)))
+        .await?;
+
+    for (scope, subject) in subjects(account, ip) {
+        let attempt = uow
+            .login_attempt()
+            .record_failure(scope, &subject, now, now - window)
+            .await?;
+        // A shared IP only starts backing off once it has failed as often as would lock an account.
+        let (free, threshold) = if scope == SCOPE_IP {
+            (cfg.lockout_threshold, cfg.ip_lockout_threshold)
+        } else {
+            (cfg.free_attempts, cfg.lockout_threshold)
+        };
+        if let Some(secs) = block_seconds(attempt.failures, free, threshold) {
+            uow.login_attempt()
+                .block(scope, &subject, now + Duration::seconds(secs))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) async fn clear(uow: &mut dyn UnitOfWork, account: &str) -> Result<bool, UseCaseError> {
+    Ok(uow.login_attempt().delete(SCOPE_ACCOUNT, account).await?)
+}
+
+pub(crate) async fn locked_until(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, UseCaseError> {
+    Ok(uow
+        .login_attempt()
+        .select(SCOPE_ACCOUNT, account)
+        .await?
+        .and_then(|a| a.blocked_until)
+        .filter(|until| *until > now))
+}
+
+// Exponential backoff (1s, 2s, 4s, ...) after the free attempts, then a lockout at the threshold.
+fn block_seconds(failures: i64, free: i64, threshold: i64) -> Option<i64> {
+    let cfg = &config::CONFIG.throttle;
+    if failures >= threshold {
+        Some(cfg.lockout_duration)
+    } else if failures > free {
+        let exponent = (failures - free - 1).min(30) as u32;
+        Some(2i64.pow(exponent).min(cfg.backoff_max))
+    } else {
+        None
+    }
+}
+
+// IPv6 clients are counted per /64, since a single host usually controls the whole prefix.
+fn subjects(account: &str, ip: Option<IpAddr>) -> Vec<(&'static str, String)> {
+    let mut subjects = vec![(SCOPE_ACCOUNT, account.to_string())];
+    match ip {
+        Some(IpAddr::V4(v4)) => subjects.push((SCOPE_IP, v4.to_string())),
+        Some(IpAddr::V6(v6)) => match v6.to_ipv4_mapped() {
+            Some(v4) => subjects.push((SCOPE_IP, v4.to_string())),
+            None => {
+                let s = v6.segments();
+                subjects.push((
+                    SCOPE_IP,
+                    format!("{:x}:{:x}:{:x}:{:x}::/64", s[0], s[1], s[2], s[3]),
+                ));
+            }
+        },
+        None => {}
+    }
+    subjects
+}
+
+fn retry_after(until: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+    let millis = (until - now).num_milliseconds().max(0) as u64;
+    millis.div_ceil(1000).max(1)
+}
@@ -1,52 +1,618 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::errors::UseCaseError;
-use crate::model::todo::{TodoDto, CreateTodoRequest};
-use domain::{UnitOfWorkProvider, model::todo::TodoEntity};
+use crate::model::todo::{
+    ChecklistItem, CreateTodoRequest, ListTodoRequest, PatchTodoRequest, SortOrder, TodoDto,
+    TodoPage, TodoTreeDto, UpdateTodoRequest,
+};
+use domain::{
+    UnitOfWork, UnitOfWorkProvider,
+    interface::clock::Clock,
+    model::recurrence::Recurrence,
+    model::tag::TagEntity,
+    model::todo::{ChecklistItemEntity, TodoCursor, TodoEntity, TodoQuery, TodoSort},
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+const MAX_TAG_FILTERS: usize = 20;
 
 pub struct TodoUseCase {
     provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+    clock: Arc<dyn Clock>,
 }
 
 impl TodoUseCase {
-    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>) -> Self {
-        Self { provider }
+    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>, clock: Arc<dyn Clock>) -> Self {
+        Self { provider, clock }
     }
 
-    pub async fn create(&self, dto: CreateTodoRequest) -> Result<TodoDto, UseCaseError> {
+    pub async fn create(
+        &self,
+        account: &str,
+        dto: CreateTodoRequest,
+    ) -> Result<TodoDto, UseCaseError> {
         let mut uow = self.provider.begin().await?;
+        let tags = resolve_tags(uow.as_mut(), account, &dto.tags).await?;
+        if let Some(parent_id) = dto.parent_id {
+            check_parent(uow.as_mut(), account, None, parent_id).await?;
+        }
 
         let entity = TodoEntity {
             id: 0,
-            account: dto.account.clone(),
+            account: account.to_string(),
             due_date: dto.due_date,
             content: dto.content.clone(),
             complete: dto.complete,
+            public: dto.public,
+            priority: dto.priority.level(),
+            parent_id: dto.parent_id,
+            recurrence: canonical_recurrence(dto.recurrence.as_deref())?,
         };
 
         let entity = uow.todo().insert(&entity).await?;
+        set_tags(uow.as_mut(), entity.id, tags).await?;
+        set_checklist(uow.as_mut(), entity.id, &dto.checklist).await?;
+        rollup(uow.as_mut(), entity.parent_id).await?;
+        let res = to_dtos(uow.as_mut(), vec![entity]).await?.remove(0);
 
         uow.commit().await?;
 
-        Ok(TodoDto {
-            id: entity.id,
-            account: entity.account,
-            due_date: entity.due_date,
-            content: entity.content,
-            complete: entity.complete,
-        })
+        Ok(res)
     }
 
-    pub async fn find(&self, id: i64) -> Result<Option<TodoDto>, UseCaseError> {
+    pub async fn find(
+        &self,
+        account: Option<&str>,
+        id: i64,
+    ) -> Result<Option<TodoDto>, UseCaseError> {
         let mut uow = self.provider.begin().await?;
         let entity = uow.todo().selectl(id).await?;
+        let entities = entity
+            .into_iter()
+            .filter(|e| can_read(e, account))
+            .collect();
+        let dtos = to_dtos(uow.as_mut(), entities).await?;
+        uow.commit().await?;
+        Ok(dtos.into_iter().next())
+    }
+
+    // Subtasks the viewer cannot read are left out together with everything below them.
+    pub async fn tree(
+        &self,
+        account: Option<&str>,
+        id: i64,
+    ) -> Result<Option<TodoTreeDto>, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+        let root = match uow.todo().selectl(id).await? {
+            Some(e) if can_read(&e, account) => e,
+            _ => return Ok(None),
+        };
+
+        let mut visited = HashSet::from([root.id]);
+        let mut frontier = vec![root.id];
+        let mut entities = vec![root];
+        while !frontier.is_empty() {
+            let children: Vec<TodoEntity> = uow
+                .todo()
+                .select_by_parents(&frontier)
+                .await?
+                .into_iter()
+                .filter(|e| can_read(e, account) && visited.insert(e.id))
+                .collect();
+            frontier = children.iter().map(|e| e.id).collect();
+            entities.extend(children);
+        }
+        let mut dtos = to_dtos(uow.as_mut(), entities).await?.into_iter();
+        uow.commit().await?;
+
+        let Some(root) = dtos.next() else {
+            return Ok(None);
+        };
+        let mut children: HashMap<i64, Vec<TodoDto>> = HashMap::new();
+        for dto in dtos {
+            if let Some(parent_id) = dto.parent_id {
+                children.entry(parent_id).or_default().push(dto);
+            }
+        }
+        Ok(Some(build_tree(root, &mut children)))
+    }
+
+    pub async fn list(
+        &self,
+        account: Option<&str>,
+        dto: ListTodoRequest,
+    ) -> Result<TodoPage, UseCaseError> {
+        if dto.cursor.is_some() && dto.offset.is_some() {
+            return Err(UseCaseError::BadRequest(
+                "cursor and offset cannot be combined".to_string(),
+            ));
+        }
+        let mut tags: Vec<String> = dto
+            .tags
+            .iter()
+            .flat_map(|t| t.split(','))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect();
+        tags.sort();
+        tags.dedup();
+        if tags.len() > MAX_TAG_FILTERS {
+            return Err(UseCaseError::BadRequest(format!(
+                "at most {MAX_TAG_FILTERS} tags can be filtered"
+            )));
+        }
+        let limit = dto
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let mut query = TodoQuery {
+            viewer: account.map(str::to_string),
+            account: dto.account,
+            complete: dto.complete,
+            due_from: dto.due_from,
+            due_to: dto.due_to,
+            tags,
+            tag_match: dto.tag_match,
+            sort: dto.sort,
+            descending: dto.order == SortOrder::Desc,
+            cursor: None,
+            offset: dto.offset.unwrap_or(0).max(0),
+            limit: limit + 1,
+        };
+        let filter = filter_digest(&query)?;
+        if let Some(cursor) = dto.cursor.as_deref() {
+            let cursor = decode_cursor(cursor)?;
+            if cursor.sort != query.sort
+                || cursor.descending != query.descending
+                || cursor.filter != filter
+            {
+                return Err(UseCaseError::BadRequest(
+                    "cursor was issued for a different sort, order or filter".to_string(),
+                ));
+            }
+            if query.sort == TodoSort::DueDate && cursor.due_date.is_none() {
+                return Err(UseCaseError::BadRequest("Invalid cursor".to_string()));
+            }
+            query.cursor = Some(cursor);
+        }
+
+        let mut uow = self.provider.begin().await?;
+        let mut entities = uow.todo().select_page(&query).await?;
+
+        let has_more = entities.len() as i64 > limit;
+        entities.truncate(limit as usize);
+
+        let next_cursor = match entities.last() {
+            Some(last) if has_more => Some(encode_cursor(&TodoCursor {
+                sort: query.sort,
+                descending: query.descending,
+                filter,
+                due_date: (query.sort == TodoSort::DueDate).then_some(last.due_date),
+                id: last.id,
+            })?),
+            _ => None,
+        };
+
+        let items = to_dtos(uow.as_mut(), entities).await?;
+        uow.commit().await?;
+
+        Ok(TodoPage {
+            items,
+            next_cursor,
+            has_more,
+        })
+    }
+
+    pub async fn update(
+        &self,
+        account: &str,
+        id: i64,
+        dto: UpdateTodoRequest,
+    ) -> Result<TodoDto, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let entity = match uow.todo().selectl(id).await? {
+            Some(e) if can_read(&e, Some(account)) => e,
+            _ => return Err(UseCaseError::NotFound),
+        };
+        ensure_owner(&entity, account)?;
+        let tags = resolve_tags(uow.as_mut(), account, &dto.tags).await?;
+        if let Some(parent_id) = dto.parent_id {
+            check_parent(uow.as_mut(), account, Some(id), parent_id).await?;
+        }
+        let old_parent_id = entity.parent_id;
+        let was_complete = entity.complete;
+
+        let entity = TodoEntity {
+            due_date: dto.due_date,
+            content: dto.content,
+            complete: dto.complete,
+            public: dto.public,
+            priority: dto.priority.level(),
+            parent_id: dto.parent_id,
+            recurrence: canonical_recurrence(dto.recurrence.as_deref())?,
+            ..entity
+        };
+
+        if uow.todo().update(&entity).await?.is_none() {
+            return Err(UseCaseError::NotFound);
+        }
+        set_tags(uow.as_mut(), id, tags).await?;
+        set_checklist(uow.as_mut(), id, &dto.checklist).await?;
+        let now = self.clock.now();
+        let res = finish_write(uow.as_mut(), id, old_parent_id, was_complete, now).await?;
+        uow.commit().await?;
+
+        Ok(res)
+    }
+
+    pub async fn patch(
+        &self,
+        account: &str,
+        id: i64,
+        dto: PatchTodoRequest,
+    ) -> Result<TodoDto, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let entity = match uow.todo().selectl(id).await? {
+            Some(e) if can_read(&e, Some(account)) => e,
+            _ => return Err(UseCaseError::NotFound),
+        };
+        ensure_owner(&entity, account)?;
+        let tags = match &dto.tags {
+            Some(names) => Some(resolve_tags(uow.as_mut(), account, names).await?),
+            None => None,
+        };
+        if let Some(Some(parent_id)) = dto.parent_id {
+            check_parent(uow.as_mut(), account, Some(id), parent_id).await?;
+        }
+        let old_parent_id = entity.parent_id;
+        let was_complete = entity.complete;
+        let recurrence = match dto.recurrence {
+            Some(rule) => canonical_recurrence(rule.as_deref())?,
+            None => entity.recurrence.clone(),
+        };
+
+        let entity = TodoEntity {
+            due_date: dto.due_date.unwrap_or(entity.due_date),
+            content: dto.content.unwrap_or(entity.content),
+            complete: dto.complete.unwrap_or(entity.complete),
+            public: dto.public.unwrap_or(entity.public),
+            priority: dto.priority.map_or(entity.priority, |p| p.level()),
+            parent_id: dto.parent_id.unwrap_or(entity.parent_id),
+            recurrence,
+            ..entity
+        };
+
+        if uow.todo().update(&entity).await?.is_none() {
+            return Err(UseCaseError::NotFound);
+        }
+        if let Some(tags) = tags {
+            set_tags(uow.as_mut(), id, tags).await?;
+        }
+        if let Some(checklist) = &dto.checklist {
+            set_checklist(uow.as_mut(), id, checklist).await?;
+        }
+        let now = self.clock.now();
+        let res = finish_write(uow.as_mut(), id, old_parent_id, was_complete, now).await?;
+        uow.commit().await?;
+
+        Ok(res)
+    }
+
+    // Subtasks of a deleted todo are kept and move up to the top level.
+    pub async fn delete(&self, account: &str, id: i64) -> Result<TodoDto, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let entity = match uow.todo().selectl(id).await? {
+            Some(e) if can_read(&e, Some(account)) => e,
+            _ => return Err(UseCaseError::NotFound),
+        };
+        ensure_owner(&entity, account)?;
+
+        let res = to_dtos(uow.as_mut(), vec![entity.clone()]).await?.remove(0);
+        uow.tag().unassign_by_todo(id).await?;
+        uow.checklist().delete_by_todo(id).await?;
+        uow.todo().detach_children(id).await?;
+        let deleted = uow.todo().delete(id).await?;
+        rollup(uow.as_mut(), entity.parent_id).await?;
         uow.commit().await?;
-        Ok(entity.map(|e| TodoDto {
-            id: e.id,
-            account: e.account,
-            due_date: e.due_date,
-            content: e.content,
-            complete: e.complete,
-        }))
+        deleted.map(|_| res).ok_or(UseCaseError::NotFound)
+    }
+}
+
+// Looks up the member's own tags by name; unknown names are rejected rather than created.
+async fn resolve_tags(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    names: &[String],
+) -> Result<Vec<TagEntity>, UseCaseError> {
+    let mut names: Vec<String> = names.iter().map(|n| n.trim().to_string()).collect();
+    names.sort();
+    names.dedup();
+    let tags = uow.tag().select_by_names(account, &names).await?;
+    if let Some(unknown) = names.iter().find(|n| !tags.iter().any(|t| &t.name == *n)) {
+        return Err(UseCaseError::BadRequest(format!("Unknown tag: {unknown}")));
+    }
+    Ok(tags)
+}
+
+// Makes `tags` the exact tag set of the todo.
+async fn set_tags(
+    uow: &mut dyn UnitOfWork,
+    todo_id: i64,
+    tags: Vec<TagEntity>,
+) -> Result<(), UseCaseError> {
+    let current = uow.tag().select_by_todos(&[todo_id]).await?;
+    for assigned in &current {
+        if !tags.iter().any(|t| t.id == assigned.tag_id) {
+            uow.tag().unassign(todo_id, assigned.tag_id).await?;
+        }
+    }
+    for tag in &tags {
+        if !current.iter().any(|a| a.tag_id == tag.id) {
+            uow.tag().assign(todo_id, tag.id).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn set_checklist(
+    uow: &mut dyn UnitOfWork,
+    todo_id: i64,
+    items: &[ChecklistItem],
+) -> Result<(), UseCaseError> {
+    uow.checklist().delete_by_todo(todo_id).await?;
+    for (position, item) in items.iter().enumerate() {
+        let entity = ChecklistItemEntity {
+            id: 0,
+            todo_id,
+            position: position as i32,
+            content: item.content.trim().to_string(),
+            checked: item.checked,
+        };
+        uow.checklist().insert(&entity).await?;
+    }
+    Ok(())
+}
+
+// The parent has to be another todo of the member, and an existing todo cannot be moved
+// below itself or one of its own subtasks.
+async fn check_parent(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    id: Option<i64>,
+    parent_id: i64,
+) -> Result<(), UseCaseError> {
+    let mut ancestor = match uow.todo().selectl(parent_id).await? {
+        Some(parent) if parent.account == account => Some(parent),
+        _ => {
+            return Err(UseCaseError::BadRequest(format!(
+                "Unknown parent todo: {parent_id}"
+            )));
+        }
+    };
+    let mut visited = HashSet::new();
+    while let Some(todo) = ancestor {
+        if Some(todo.id) == id {
+            return Err(UseCaseError::BadRequest(
+                "A todo cannot be a subtask of itself or of its own subtasks".to_string(),
+            ));
+        }
+        if !visited.insert(todo.id) {
+            break;
+        }
+        ancestor = match todo.parent_id {
+            Some(parent_id) => uow.todo().selectl(parent_id).await?,
+            None => None,
+        };
+    }
+    Ok(())
+}
+
+// A todo with subtasks is complete exactly when all of them are; starting from `id`, the
+// derived state is stored on every todo up to the root.
+async fn rollup(uow: &mut dyn UnitOfWork, id: Option<i64>) -> Result<(), UseCaseError> {
+    let mut visited = HashSet::new();
+    let mut next = id;
+    while let Some(id) = next {
+        if !visited.insert(id) {
+            break;
+        }
+        let Some(entity) = uow.todo().selectl(id).await? else {
+            break;
+        };
+        let children = uow.todo().select_by_parents(&[id]).await?;
+        let complete = children.iter().all(|c| c.complete);
+        if !children.is_empty() && complete != entity.complete {
+            uow.todo()
+                .update(&TodoEntity {
+                    complete,
+                    ..entity.clone()
+                })
+                .await?;
+        }
+        next = entity.parent_id;
+    }
+    Ok(())
+}
+
+// Rolls completion up from the written todo and, when it moved, from its former parent,
+// then schedules the next occurrence if the write completed a recurring todo.
+async fn finish_write(
+    uow: &mut dyn UnitOfWork,
+    id: i64,
+    old_parent_id: Option<i64>,
+    was_complete: bool,
+    now: DateTime<Utc>,
+) -> Result<TodoDto, UseCaseError> {
+    rollup(uow, Some(id)).await?;
+    let mut entity = match uow.todo().selectl(id).await? {
+        Some(e) => e,
+        None => return Err(UseCaseError::NotFound),
+    };
+    if old_parent_id != entity.parent_id {
+        rollup(uow, old_parent_id).await?;
+    }
+    if entity.complete && !was_complete && entity.recurrence.is_some() {
+        entity = repeat(uow, entity, now).await?;
+    }
+    Ok(to_dtos(uow, vec![entity]).await?.remove(0))
+}
+
+// The rule moves from the completed todo to a copy due at the next occurrence, with the
+// same tags and an unchecked checklist; subtasks are not copied. Occurrences that are
+// already past at `now` are skipped. Dates follow the member's time zone, or UTC.
+async fn repeat(
+    uow: &mut dyn UnitOfWork,
+    entity: TodoEntity,
+    now: DateTime<Utc>,
+) -> Result<TodoEntity, UseCaseError> {
+    let rule = entity
+        .recurrence
+        .as_deref()
+        .and_then(|r| r.parse::<Recurrence>().ok());
+    let completed = TodoEntity {
+        recurrence: None,
+        ..entity
+    };
+    uow.todo().update(&completed).await?;
+    let Some(rule) = rule else {
+        return Ok(completed);
+    };
+
+    let timezone = uow
+        .member()
+        .select(&completed.account)
+        .await?
+        .and_then(|m| m.timezone)
+        .and_then(|tz| tz.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC);
+    let Some(next) = rule.next_occurrence(completed.due_date, timezone, now) else {
+        return Ok(completed);
+    };
+
+    let copy = uow
+        .todo()
+        .insert(&TodoEntity {
+            id: 0,
+            due_date: next.due_date,
+            complete: false,
+            recurrence: Some(next.recurrence.to_string()),
+            ..completed.clone()
+        })
+        .await?;
+    let tags = uow.tag().select_by_todos(&[completed.id]).await?;
+    for assigned in tags {
+        uow.tag().assign(copy.id, assigned.tag_id).await?;
+    }
+    let checklist = uow.checklist().select_by_todos(&[completed.id]).await?;
+    for item in checklist {
+        uow.checklist()
+            .insert(&ChecklistItemEntity {
+                id: 0,
+                todo_id: copy.id,
+                checked: false,
+                ..item
+            })
+            .await?;
+    }
+    rollup(uow, copy.parent_id).await?;
+    Ok(completed)
+}
+
+fn canonical_recurrence(rule: Option<&str>) -> Result<Option<String>, UseCaseError> {
+    rule.map(|r| {
+        r.parse::<Recurrence>()
+            .map(|r| r.to_string())
+            .map_err(UseCaseError::BadRequest)
+    })
+    .transpose()
+}
+
+fn build_tree(todo: TodoDto, children: &mut HashMap<i64, Vec<TodoDto>>) -> TodoTreeDto {
+    let subtasks = children.remove(&todo.id).unwrap_or_default();
+    TodoTreeDto {
+        children: subtasks
+            .into_iter()
+            .map(|c| build_tree(c, children))
+            .collect(),
+        todo,
+    }
+}
+
+async fn to_dtos(
+    uow: &mut dyn UnitOfWork,
+    entities: Vec<TodoEntity>,
+) -> Result<Vec<TodoDto>, UseCaseError> {
+    let ids: Vec<i64> = entities.iter().map(|e| e.id).collect();
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for assigned in uow.tag().select_by_todos(&ids).await? {
+        tags.entry(assigned.todo_id)
+            .or_default()
+            .push(assigned.name);
+    }
+    let mut checklists: HashMap<i64, Vec<ChecklistItemEntity>> = HashMap::new();
+    for item in uow.checklist().select_by_todos(&ids).await? {
+        checklists.entry(item.todo_id).or_default().push(item);
+    }
+    Ok(entities
+        .into_iter()
+        .map(|e| {
+            let names = tags.remove(&e.id).unwrap_or_default();
+            let checklist = checklists.remove(&e.id).unwrap_or_default();
+            TodoDto::new(e, names, checklist)
+        })
+        .collect())
+}
+
+fn encode_cursor(cursor: &TodoCursor) -> Result<String, UseCaseError> {
+    let json = serde_json::to_vec(cursor).map_err(|e| UseCaseError::Infrastructure(Box::new(e)))?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+// A cursor only continues the listing it came from; replaying it with other filters
+// would silently skip or repeat todos.
+fn filter_digest(query: &TodoQuery) -> Result<String, UseCaseError> {
+    let filters = (
+        &query.viewer,
+        &query.account,
+        query.complete,
+        query.due_from,
+        query.due_to,
+        &query.tags,
+        query.tag_match,
+    );
+    let json =
+        serde_json::to_vec(&filters).map_err(|e| UseCaseError::Infrastructure(Box::new(e)))?;
+    Ok(URL_SAFE_NO_PAD.encode(&Sha256::digest(json)[..12]))
+}
+
+fn decode_cursor(cursor: &str) -> Result<TodoCursor, UseCaseError> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .ok_or_else(|| UseCaseError::BadRequest("Invalid cursor".to_string()))
+}
+
+// Todos the caller cannot read are reported as missing, so a private todo's id does not
+// reveal that it exists; only public todos of other members get Forbidden on writes.
+fn can_read(entity: &TodoEntity, account: Option<&str>) -> bool {
+    entity.public || account == Some(entity.account.as_str())
+}
+
+fn ensure_owner(entity: &TodoEntity, account: &str) -> Result<(), UseCaseError> {
+    if entity.account != account {
+        return Err(UseCaseError::Forbidden);
     }
+    Ok(())
 }
@@ -0,0 +1,250 @@
+use chrono::Utc;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::errors::UseCaseError;
+use crate::model::two_factor::{
+    RecoveryCodesResponse, TotpConfirmRequest, TotpEnrollResponse, TwoFactorReauthRequest,
+    TwoFactorStatusResponse,
+};
+use crate::usecase::throttle;
+use domain::{
+    UnitOfWork, UnitOfWorkProvider,
+    model::two_factor::{RecoveryCodeEntity, TotpEntity},
+};
+
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub struct TwoFactorUseCase {
+    provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+}
+
+impl TwoFactorUseCase {
+    pub fn new(provider: Arc<dyn UnitOfWorkProvider + Send + Sync>) -> Self {
+        Self { provider }
+    }
+
+    pub async fn status(&self, account: &str) -> Result<TwoFactorStatusResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let enabled = is_enabled(uow.as_mut(), account).await?;
+        let remaining = if enabled {
+            uow.two_factor()
+                .select_unused_recovery_codes(account)
+                .await?
+                .len()
+        } else {
+            0
+        };
+
+        Ok(TwoFactorStatusResponse {
+            enabled,
+            recovery_codes_remaining: remaining,
+        })
+    }
+
+    // Starts (or restarts) enrollment; the secret only takes effect once confirmed.
+    pub async fn enroll(&self, account: &str) -> Result<TotpEnrollResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        if is_enabled(uow.as_mut(), account).await? {
+            return Err(UseCaseError::TwoFactorEnabled);
+        }
+
+        let secret = simple_totp::generate_secret();
+        let entity = TotpEntity {
+            account: account.to_string(),
+            secret: secret.clone(),
+            confirmed: false,
+            last_step: 0,
+        };
+        uow.two_factor().upsert_totp(&entity).await?;
+        uow.commit().await?;
+
+        Ok(TotpEnrollResponse {
+            otpauth_uri: simple_totp::otpauth_uri(&config::CONFIG.totp.issuer, account, &secret),
+            secret,
+        })
+    }
+
+    pub async fn confirm(
+        &self,
+        account: &str,
+        dto: TotpConfirmRequest,
+    ) -> Result<RecoveryCodesResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        let totp = match uow.two_factor().select_totp(account).await? {
+            Some(t) if t.confirmed => return Err(UseCaseError::TwoFactorEnabled),
+            Some(t) => t,
+            None => return Err(UseCaseError::TwoFactorNotEnabled),
+        };
+        let step = match simple_totp::verify(&totp.secret, &dto.code, unix_now()) {
+            Some(step) => step as i64,
+            None => {
+                return Err(UseCaseError::BadRequest(
+                    "Invalid verification code".to_string(),
+                ));
+            }
+        };
+        if !uow.two_factor().confirm_totp(account, step).await? {
+            return Err(UseCaseError::TwoFactorEnabled);
+        }
+
+        let recovery_codes = replace_recovery_codes(uow.as_mut(), account).await?;
+        uow.commit().await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    pub async fn regenerate_recovery_codes(
+        &self,
+        account: &str,
+        dto: TwoFactorReauthRequest,
+        ip: Option<IpAddr>,
+    ) -> Result<RecoveryCodesResponse, UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        if let Err(e) = reauthenticate(uow.as_mut(), account, dto, ip).await {
+            uow.commit().await?;
+            return Err(e);
+        }
+        let recovery_codes = replace_recovery_codes(uow.as_mut(), account).await?;
+        uow.commit().await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    pub async fn disable(
+        &self,
+        account: &str,
+        dto: TwoFactorReauthRequest,
+        ip: Option<IpAddr>,
+    ) -> Result<(), UseCaseError> {
+        let mut uow = self.provider.begin().await?;
+
+        if let Err(e) = reauthenticate(uow.as_mut(), account, dto, ip).await {
+            uow.commit().await?;
+            return Err(e);
+        }
+        delete_all(uow.as_mut(), account).await?;
+        uow.commit().await?;
+
+        Ok(())
+    }
+}
+
+// Accepts either a TOTP code (each time step only once) or an unused recovery code.
+pub(crate) async fn verify_code(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    code: &str,
+) -> Result<bool, UseCaseError> {
+    let code = code.trim();
+    if code.len() == simple_totp::DIGITS && code.bytes().all(|b| b.is_ascii_digit()) {
+        let totp = match uow.two_factor().select_totp(account).await? {
+            Some(t) if t.confirmed => t,
+            _ => return Ok(false),
+        };
+        return match simple_totp::verify(&totp.secret, code, unix_now()) {
+            Some(step) => Ok(uow
+                .two_factor()
+                .advance_totp_step(account, step as i64)
+                .await?),
+            None => Ok(false),
+        };
+    }
+
+    let code = normalize_recovery_code(code);
+    let unused = uow
+        .two_factor()
+        .select_unused_recovery_codes(account)
+        .await?;
+    for entity in unused {
+        if async_argon2::verify(code.clone(), entity.code_hash.clone()).await? {
+            return Ok(uow
+                .two_factor()
+                .use_recovery_code(account, &entity.code_hash)
+                .await?);
+        }
+    }
+    Ok(false)
+}
+
+pub(crate) async fn is_enabled(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+) -> Result<bool, UseCaseError> {
+    Ok(uow
+        .two_factor()
+        .select_totp(account)
+        .await?
+        .is_some_and(|t| t.confirmed))
+}
+
+pub(crate) async fn delete_all(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+) -> Result<(), UseCaseError> {
+    uow.two_factor().delete_totp(account).await?;
+    uow.two_factor().delete_recovery_codes(account).await?;
+    Ok(())
+}
+
+// Throttled like signin, so a stolen session cannot be used to guess the password or codes.
+// Failures are recorded in `uow`, which the caller commits even when this returns an error.
+async fn reauthenticate(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+    dto: TwoFactorReauthRequest,
+    ip: Option<IpAddr>,
+) -> Result<(), UseCaseError> {
+    let now = Utc::now();
+    throttle::check(uow, account, ip, now).await?;
+    if !is_enabled(uow, account).await? {
+        return Err(UseCaseError::TwoFactorNotEnabled);
+    }
+    let member = match uow.member().select(account).await? {
+        Some(m) => m,
+        None => return Err(UseCaseError::Unauthorized),
+    };
+    if !async_argon2::verify(dto.password, member.password).await?
+        || !verify_code(uow, account, &dto.code).await?
+    {
+        throttle::record_failure(uow, account, ip, now).await?;
+        return Err(UseCaseError::InvalidCredentials);
+    }
+    throttle::clear(uow, account).await?;
+    Ok(())
+}
+
+async fn replace_recovery_codes(
+    uow: &mut dyn UnitOfWork,
+    account: &str,
+) -> Result<Vec<String>, UseCaseError> {
+    uow.two_factor().delete_recovery_codes(account).await?;
+
+    let mut codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+    for _ in 0..RECOVERY_CODE_COUNT {
+        let code = simple_totp::generate_recovery_code();
+        let entity = RecoveryCodeEntity {
+            account: account.to_string(),
+            code_hash: async_argon2::hash(normalize_recovery_code(&code)).await?,
+            used: false,
+        };
+        uow.two_factor().insert_recovery_code(&entity).await?;
+        codes.push(code);
+    }
+    Ok(codes)
+}
+
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn unix_now() -> u64 {
+    Utc::now().timestamp() as u64
+}
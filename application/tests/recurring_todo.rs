@@ -0,0 +1,245 @@
+use application::errors::UseCaseError;
+use application::model::tag::CreateTagRequest;
+use application::model::todo::{
+    ChecklistItem, CreateTodoRequest, ListTodoRequest, PatchTodoRequest, Priority, TodoDto,
+};
+use application::usecase::{tag::TagUseCase, todo::TodoUseCase};
+use chrono::{DateTime, Utc};
+use common::{migrate, setup::connect};
+use domain::{UnitOfWorkProvider, interface::clock::Clock, model::member::MemberEntity};
+use infrastructure::UnitOfWorkProviderImpl;
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const ACCOUNT: &str = "user1";
+
+struct FixedClock(DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+// A migrated SQLite database in a file of its own, removed when dropped.
+struct TestDb {
+    path: PathBuf,
+    provider: Arc<dyn UnitOfWorkProvider + Send + Sync>,
+}
+
+impl TestDb {
+    async fn new(timezone: Option<&str>) -> Self {
+        let path = std::env::temp_dir().join(format!("recurring-{}.db", Uuid::new_v4()));
+        let dsn = format!("sqlite://{}?mode=rwc", path.display());
+        let pool = connect(&dsn).await.unwrap();
+        let source = concat!(env!("CARGO_MANIFEST_DIR"), "/../migrations/sqlite");
+        let migrator = migrate::load(&pool, source).await.unwrap();
+        migrate::up(&pool, &migrator).await.unwrap();
+        pool.close().await;
+
+        let provider = Arc::new(UnitOfWorkProviderImpl::new(connect(&dsn).await.unwrap()));
+        let mut uow = provider.begin().await.unwrap();
+        let member = MemberEntity {
+            account: ACCOUNT.to_string(),
+            password: "-".to_string(),
+            disabled: false,
+            email: None,
+            verified: true,
+            verification_sent_at: None,
+            timezone: None,
+        };
+        uow.member().insert(&member).await.unwrap();
+        uow.member()
+            .update_timezone(ACCOUNT, timezone)
+            .await
+            .unwrap();
+        uow.commit().await.unwrap();
+
+        Self { path, provider }
+    }
+
+    fn todos(&self, now: &str) -> TodoUseCase {
+        TodoUseCase::new(self.provider.clone(), Arc::new(FixedClock(utc(now))))
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        for suffix in ["", "-shm", "-wal"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", self.path.display()));
+        }
+    }
+}
+
+fn utc(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+fn request(due_date: &str, recurrence: &str) -> CreateTodoRequest {
+    CreateTodoRequest {
+        due_date: utc(due_date),
+        content: "water the plants".to_string(),
+        complete: false,
+        public: false,
+        tags: Vec::new(),
+        priority: Priority::Normal,
+        parent_id: None,
+        checklist: Vec::new(),
+        recurrence: Some(recurrence.to_string()),
+    }
+}
+
+fn complete() -> PatchTodoRequest {
+    PatchTodoRequest {
+        due_date: None,
+        content: None,
+        complete: Some(true),
+        public: None,
+        tags: None,
+        priority: None,
+        parent_id: None,
+        checklist: None,
+        recurrence: None,
+    }
+}
+
+async fn open_todos(todos: &TodoUseCase) -> Vec<TodoDto> {
+    let query = ListTodoRequest {
+        complete: Some(false),
+        ..Default::default()
+    };
+    todos.list(Some(ACCOUNT), query).await.unwrap().items
+}
+
+#[tokio::test]
+async fn completing_a_recurring_todo_creates_the_next_occurrence() {
+    let db = TestDb::new(None).await;
+    let todos = db.todos("2024-01-01T12:00:00Z");
+    TagUseCase::new(db.provider.clone())
+        .create(
+            ACCOUNT,
+            CreateTagRequest {
+                name: "home".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let todo = todos
+        .create(
+            ACCOUNT,
+            CreateTodoRequest {
+                tags: vec!["home".to_string()],
+                checklist: vec![ChecklistItem {
+                    content: "kitchen".to_string(),
+                    checked: true,
+                }],
+                ..request("2024-01-01T09:00:00Z", "RRULE:freq=daily;count=3")
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(todo.recurrence.as_deref(), Some("FREQ=DAILY;COUNT=3"));
+
+    let completed = todos.patch(ACCOUNT, todo.id, complete()).await.unwrap();
+    assert!(completed.complete);
+    assert_eq!(completed.recurrence, None);
+
+    let next = open_todos(&todos).await;
+    assert_eq!(next.len(), 1);
+    let next = &next[0];
+    assert_ne!(next.id, todo.id);
+    assert_eq!(next.due_date, utc("2024-01-02T09:00:00Z"));
+    assert_eq!(next.recurrence.as_deref(), Some("FREQ=DAILY;COUNT=2"));
+    assert_eq!(next.content, todo.content);
+    assert_eq!(next.tags, vec!["home".to_string()]);
+    assert_eq!(next.checklist.len(), 1);
+    assert!(!next.checklist[0].checked);
+
+    // Completing the same todo again does not repeat it twice.
+    let mut undo = complete();
+    undo.complete = Some(false);
+    todos.patch(ACCOUNT, todo.id, undo).await.unwrap();
+    todos.patch(ACCOUNT, todo.id, complete()).await.unwrap();
+    let open = open_todos(&todos).await;
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].id, next.id);
+}
+
+#[tokio::test]
+async fn the_series_ends_with_its_count() {
+    let db = TestDb::new(None).await;
+    let todos = db.todos("2024-01-01T12:00:00Z");
+
+    let todo = todos
+        .create(
+            ACCOUNT,
+            request("2024-01-01T09:00:00Z", "FREQ=WEEKLY;COUNT=2"),
+        )
+        .await
+        .unwrap();
+    todos.patch(ACCOUNT, todo.id, complete()).await.unwrap();
+    let next = open_todos(&todos).await.remove(0);
+    assert_eq!(next.due_date, utc("2024-01-08T09:00:00Z"));
+    assert_eq!(next.recurrence.as_deref(), Some("FREQ=WEEKLY;COUNT=1"));
+
+    todos.patch(ACCOUNT, next.id, complete()).await.unwrap();
+    assert!(open_todos(&todos).await.is_empty());
+}
+
+#[tokio::test]
+async fn occurrences_already_past_are_skipped() {
+    let db = TestDb::new(None).await;
+    let todo = db
+        .todos("2024-01-01T00:00:00Z")
+        .create(ACCOUNT, request("2024-01-01T09:00:00Z", "daily"))
+        .await
+        .unwrap();
+
+    // Completed ten days late, in the afternoon.
+    let todos = db.todos("2024-01-11T15:00:00Z");
+    todos.patch(ACCOUNT, todo.id, complete()).await.unwrap();
+    let next = open_todos(&todos).await.remove(0);
+    assert_eq!(next.due_date, utc("2024-01-12T09:00:00Z"));
+}
+
+#[tokio::test]
+async fn the_member_timezone_keeps_the_local_time() {
+    let db = TestDb::new(Some("America/New_York")).await;
+    let todos = db.todos("2024-03-09T12:00:00Z");
+
+    // 09:00 EST on the day before the switch to daylight saving time
+    let todo = todos
+        .create(ACCOUNT, request("2024-03-09T14:00:00Z", "daily"))
+        .await
+        .unwrap();
+    todos.patch(ACCOUNT, todo.id, complete()).await.unwrap();
+    let next = open_todos(&todos).await.remove(0);
+    assert_eq!(next.due_date, utc("2024-03-10T13:00:00Z"));
+}
+
+#[tokio::test]
+async fn due_dates_stay_in_utc_without_a_timezone() {
+    let db = TestDb::new(None).await;
+    let todos = db.todos("2024-03-09T12:00:00Z");
+
+    let todo = todos
+        .create(ACCOUNT, request("2024-03-09T14:00:00Z", "daily"))
+        .await
+        .unwrap();
+    todos.patch(ACCOUNT, todo.id, complete()).await.unwrap();
+    let next = open_todos(&todos).await.remove(0);
+    assert_eq!(next.due_date, utc("2024-03-10T14:00:00Z"));
+}
+
+#[tokio::test]
+async fn unsupported_rules_are_rejected() {
+    let db = TestDb::new(None).await;
+    let todos = db.todos("2024-01-01T00:00:00Z");
+
+    let result = todos
+        .create(ACCOUNT, request("2024-01-01T09:00:00Z", "FREQ=HOURLY"))
+        .await;
+    assert!(matches!(result, Err(UseCaseError::BadRequest(_))));
+}
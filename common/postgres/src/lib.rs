@@ -1,2 +0,0 @@
-pub mod setup;
-pub mod types;
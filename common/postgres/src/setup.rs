@@ -1,23 +0,0 @@
-use crate::types::{BoxError, DbPool};
-
-use sqlx::Executor;
-use std::str::FromStr;
-
-use sqlx::postgres::PgConnectOptions;
-
-pub async fn init_db(dsn: &str) -> Result<DbPool, BoxError> {
-    let options = PgConnectOptions::from_str(dsn)?;
-    let pool = DbPool::connect_with(options).await?;
-
-    if let Some(file) = &config::CONFIG.database.migration {
-        if let Ok(ddl) = tokio::fs::read_to_string(file).await {
-            for stmt in ddl.split(';') {
-                let stmt = stmt.trim();
-                if !stmt.is_empty() {
-                    pool.execute(stmt).await?;
-                }
-            }
-        }
-    }
-    Ok(pool)
-}
@@ -0,0 +1,5 @@
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub mod migrate;
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub mod setup;
+pub mod types;
@@ -0,0 +1,191 @@
+use crate::types::{BoxError, DbPool};
+
+use sqlx::Executor;
+use sqlx::migrate::{Migration, Migrator};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const EMBEDDED: &str = "embedded";
+
+pub async fn load(pool: &DbPool, source: &str) -> Result<Migrator, BoxError> {
+    if source == EMBEDDED {
+        return embedded(pool);
+    }
+    // A typo in the path must not start the server on an unmigrated schema.
+    if !Path::new(source).is_dir() {
+        return Err(format!("migration directory '{source}' does not exist").into());
+    }
+    Ok(Migrator::new(Path::new(source)).await?)
+}
+
+#[cfg(feature = "embed-migrations")]
+fn embedded(pool: &DbPool) -> Result<Migrator, BoxError> {
+    match pool {
+        #[cfg(feature = "sqlite")]
+        DbPool::Sqlite(_) => Ok(sqlx::migrate!("../migrations/sqlite")),
+        #[cfg(feature = "postgres")]
+        DbPool::Postgres(_) => Ok(sqlx::migrate!("../migrations/postgres")),
+    }
+}
+
+#[cfg(not(feature = "embed-migrations"))]
+fn embedded(_: &DbPool) -> Result<Migrator, BoxError> {
+    Err("this binary was built without the `embed-migrations` feature".into())
+}
+
+pub async fn up(pool: &DbPool, migrator: &Migrator) -> Result<Vec<i64>, BoxError> {
+    let applied = verify(pool, migrator).await?;
+
+    let mut versions = vec![];
+    for m in migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+    {
+        if applied.contains_key(&m.version) {
+            continue;
+        }
+        apply(pool, m).await?;
+        versions.push(m.version);
+    }
+    Ok(versions)
+}
+
+pub async fn down(pool: &DbPool, migrator: &Migrator, target: i64) -> Result<Vec<i64>, BoxError> {
+    let applied = verify(pool, migrator).await?;
+
+    let mut targets: Vec<i64> = applied.into_keys().filter(|v| *v > target).collect();
+    targets.sort_unstable_by(|a, b| b.cmp(a));
+
+    for version in &targets {
+        let m = migrator
+            .iter()
+            .find(|m| m.version == *version && m.migration_type.is_down_migration())
+            .ok_or_else(|| format!("migration {version} is not reversible"))?;
+        revert(pool, m).await?;
+    }
+    Ok(targets)
+}
+
+async fn verify(pool: &DbPool, migrator: &Migrator) -> Result<HashMap<i64, String>, BoxError> {
+    let applied = applied(pool).await?;
+
+    for (version, checksum) in &applied {
+        let m = migrator
+            .iter()
+            .find(|m| m.version == *version && m.migration_type.is_up_migration())
+            .ok_or_else(|| format!("applied migration {version} is missing from the source"))?;
+        if hex(&m.checksum) != *checksum {
+            return Err(format!(
+                "migration {version} ({}) has been modified after it was applied",
+                m.description
+            )
+            .into());
+        }
+    }
+    Ok(applied)
+}
+
+async fn applied(pool: &DbPool) -> Result<HashMap<i64, String>, BoxError> {
+    match pool {
+        #[cfg(feature = "sqlite")]
+        DbPool::Sqlite(p) => sqlite::applied(p).await,
+        #[cfg(feature = "postgres")]
+        DbPool::Postgres(p) => postgres::applied(p).await,
+    }
+}
+
+async fn apply(pool: &DbPool, m: &Migration) -> Result<(), BoxError> {
+    match pool {
+        #[cfg(feature = "sqlite")]
+        DbPool::Sqlite(p) => sqlite::apply(p, m).await,
+        #[cfg(feature = "postgres")]
+        DbPool::Postgres(p) => postgres::apply(p, m).await,
+    }
+}
+
+async fn revert(pool: &DbPool, m: &Migration) -> Result<(), BoxError> {
+    match pool {
+        #[cfg(feature = "sqlite")]
+        DbPool::Sqlite(p) => sqlite::revert(p, m).await,
+        #[cfg(feature = "postgres")]
+        DbPool::Postgres(p) => postgres::revert(p, m).await,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// The statements are identical for every backend; only the pool type and the
+// timestamp column type of the history table differ.
+macro_rules! backend {
+    ($name:ident, $pool:ty, $timestamp:literal) => {
+        mod $name {
+            use super::*;
+
+            const CREATE_HISTORY_TABLE: &str = concat!(
+                "CREATE TABLE IF NOT EXISTS _schema_migrations (
+    version BIGINT NOT NULL PRIMARY KEY,
+    description TEXT NOT NULL,
+    checksum TEXT NOT NULL,
+    applied_at ",
+                $timestamp,
+                " NOT NULL DEFAULT CURRENT_TIMESTAMP
+)"
+            );
+
+            pub(super) async fn applied(pool: &$pool) -> Result<HashMap<i64, String>, BoxError> {
+                pool.execute(CREATE_HISTORY_TABLE).await?;
+
+                let rows = sqlx::query_as::<_, (i64, String)>(
+                    "SELECT version, checksum FROM _schema_migrations",
+                )
+                .fetch_all(pool)
+                .await?;
+
+                Ok(rows.into_iter().collect())
+            }
+
+            pub(super) async fn apply(pool: &$pool, m: &Migration) -> Result<(), BoxError> {
+                let record = sqlx::query(
+                    "INSERT INTO _schema_migrations (version,description,checksum) VALUES ($1,$2,$3)",
+                )
+                .bind(m.version)
+                .bind(&*m.description)
+                .bind(hex(&m.checksum));
+
+                if m.no_tx {
+                    pool.execute(&*m.sql).await?;
+                    record.execute(pool).await?;
+                } else {
+                    let mut tx = pool.begin().await?;
+                    tx.execute(&*m.sql).await?;
+                    record.execute(&mut *tx).await?;
+                    tx.commit().await?;
+                }
+                Ok(())
+            }
+
+            pub(super) async fn revert(pool: &$pool, m: &Migration) -> Result<(), BoxError> {
+                let record =
+                    sqlx::query("DELETE FROM _schema_migrations WHERE version=$1").bind(m.version);
+
+                if m.no_tx {
+                    pool.execute(&*m.sql).await?;
+                    record.execute(pool).await?;
+                } else {
+                    let mut tx = pool.begin().await?;
+                    tx.execute(&*m.sql).await?;
+                    record.execute(&mut *tx).await?;
+                    tx.commit().await?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+backend!(sqlite, sqlx::SqlitePool, "TIMESTAMP");
+#[cfg(feature = "postgres")]
+backend!(postgres, sqlx::PgPool, "TIMESTAMPTZ");
@@ -0,0 +1,50 @@
+use crate::migrate;
+use crate::types::{BoxError, DbPool};
+
+#[cfg(feature = "postgres")]
+use sqlx::postgres::PgConnectOptions;
+#[cfg(feature = "sqlite")]
+use sqlx::sqlite::SqliteConnectOptions;
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+use std::str::FromStr;
+
+pub async fn connect(dsn: &str) -> Result<DbPool, BoxError> {
+    let scheme = dsn.split(':').next().unwrap_or_default();
+    match scheme {
+        #[cfg(feature = "sqlite")]
+        "sqlite" => {
+            let options = SqliteConnectOptions::from_str(dsn)?.create_if_missing(true);
+            Ok(DbPool::Sqlite(
+                sqlx::SqlitePool::connect_with(options).await?,
+            ))
+        }
+        #[cfg(feature = "postgres")]
+        "postgres" | "postgresql" => {
+            let options = PgConnectOptions::from_str(dsn)?;
+            Ok(DbPool::Postgres(sqlx::PgPool::connect_with(options).await?))
+        }
+        _ => Err(
+            format!("unsupported database scheme '{scheme}' (not enabled in this build)").into(),
+        ),
+    }
+}
+
+pub async fn init_db(dsn: &str) -> Result<DbPool, BoxError> {
+    if let Some(source) = &config::CONFIG.database.migration {
+        // SQLite connections keep the column layout of statements prepared before a schema
+        // change, so migrate on a throwaway pool and serve from fresh connections.
+        let pool = connect(dsn).await?;
+        let migrator = migrate::load(&pool, source).await?;
+        if let Some(target) = config::CONFIG.database.rollback {
+            for version in migrate::down(&pool, &migrator, target).await? {
+                tracing::info!("->> Reverted migration {}", version);
+            }
+        } else {
+            for version in migrate::up(&pool, &migrator).await? {
+                tracing::info!("->> Applied migration {}", version);
+            }
+        }
+        pool.close().await;
+    }
+    connect(dsn).await
+}
@@ -1,23 +1,35 @@
+use chrono::{DateTime, Utc};
 use clap::Parser;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tracing_subscriber::EnvFilter;
-use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub database: DatabaseConfig,
     pub server: ServerConfig,
     pub jwt: JwtConfig,
+    pub totp: TotpConfig,
+    pub throttle: ThrottleConfig,
+    pub signup: SignupConfig,
+    pub password: PasswordConfig,
+    pub notifier: NotifierConfig,
+    pub api_key: ApiKeyConfig,
+    pub oidc: OidcConfig,
+    pub session_cookie: SessionCookieConfig,
     pub log: LogConfig,
+    #[serde(skip)]
+    pub grant_admin: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DatabaseConfig {
     pub dsn: String,
     pub migration: Option<String>,
+    #[serde(skip)]
+    pub rollback: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,13 +38,100 @@ pub struct ServerConfig {
     pub cors: Vec<String>,
     #[serde(rename = "static")]
     pub static_dir: Option<String>,
+    pub trust_proxy: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JwtConfig {
     pub issuer: String,
-    pub secret: String,
+    pub secret: Option<String>,
+    pub algorithm: String,
+    pub private_key: Option<String>,
+    pub kid: Option<String>,
+    pub keys: Vec<JwtKeyConfig>,
     pub expire: i64,
+    pub refresh_expire: i64,
+    pub cache_ttl: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JwtKeyConfig {
+    pub kid: String,
+    #[serde(default = "default_jwt_algorithm")]
+    pub algorithm: String,
+    pub secret: Option<String>,
+    pub private_key: Option<String>,
+    pub activate_at: Option<DateTime<Utc>>,
+    pub retire_at: Option<DateTime<Utc>>,
+}
+
+fn default_jwt_algorithm() -> String {
+    "HS256".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TotpConfig {
+    pub issuer: String,
+    pub challenge_expire: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ThrottleConfig {
+    pub free_attempts: i64,
+    pub backoff_max: i64,
+    pub lockout_threshold: i64,
+    pub lockout_duration: i64,
+    pub ip_lockout_threshold: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignupConfig {
+    pub conceal_existing: bool,
+    pub require_verification: bool,
+    pub verification_expire: i64,
+    pub resend_interval: i64,
+    pub verify_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PasswordConfig {
+    pub reset_expire: i64,
+    pub hash_memory_kib: u32,
+    pub hash_iterations: u32,
+    pub hash_parallelism: u32,
+    pub min_length: u64,
+    pub min_entropy: f64,
+    pub deny_list: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotifierConfig {
+    pub kind: String,
+    pub file: Option<String>,
+    pub smtp_url: Option<String>,
+    pub from: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKeyConfig {
+    pub default_expire: i64,
+    pub max_expire: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OidcConfig {
+    pub issuer: Option<String>,
+    pub login_url: Option<String>,
+    pub code_expire: i64,
+    pub token_expire: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionCookieConfig {
+    pub enabled: bool,
+    pub name: String,
+    pub secure: bool,
+    pub same_site: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -46,18 +145,76 @@ impl Default for Config {
             database: DatabaseConfig {
                 dsn: "sqlite:data.db".to_string(),
                 migration: None,
+                rollback: None,
             },
             server: ServerConfig {
                 host: "0.0.0.0:3000".to_string(),
                 cors: vec![],
                 static_dir: None,
+                trust_proxy: false,
             },
             jwt: JwtConfig {
                 issuer: Config::exe_basename(),
-                secret: Uuid::new_v4().to_string(),
-                expire: 60 * 60 * 24,
+                secret: None,
+                algorithm: default_jwt_algorithm(),
+                private_key: None,
+                kid: None,
+                keys: vec![],
+                expire: 60 * 15,
+                refresh_expire: 60 * 60 * 24 * 30,
+                cache_ttl: 30,
+            },
+            totp: TotpConfig {
+                issuer: Config::exe_basename(),
+                challenge_expire: 60 * 5,
+            },
+            throttle: ThrottleConfig {
+                free_attempts: 3,
+                backoff_max: 60 * 5,
+                lockout_threshold: 10,
+                lockout_duration: 60 * 15,
+                ip_lockout_threshold: 100,
+            },
+            signup: SignupConfig {
+                conceal_existing: false,
+                require_verification: false,
+                verification_expire: 60 * 60 * 24,
+                resend_interval: 60,
+                verify_url: None,
+            },
+            password: PasswordConfig {
+                reset_expire: 60 * 60,
+                hash_memory_kib: 19 * 1024,
+                hash_iterations: 2,
+                hash_parallelism: 1,
+                min_length: 8,
+                min_entropy: 36.0,
+                deny_list: None,
+            },
+            notifier: NotifierConfig {
+                kind: "log".to_string(),
+                file: None,
+                smtp_url: None,
+                from: None,
+            },
+            api_key: ApiKeyConfig {
+                default_expire: 60 * 60 * 24 * 90,
+                max_expire: 60 * 60 * 24 * 365,
+            },
+            oidc: OidcConfig {
+                issuer: None,
+                login_url: None,
+                code_expire: 60,
+                token_expire: 60 * 60,
+            },
+            session_cookie: SessionCookieConfig {
+                enabled: false,
+                name: "session".to_string(),
+                secure: true,
+                same_site: "Strict".to_string(),
             },
             log: LogConfig { level: None },
+            grant_admin: None,
         }
     }
 }
@@ -78,18 +235,17 @@ pub static CONFIG: Lazy<Config> = Lazy::new(|| {
     ];
 
     for path in paths {
-        if Path::new(&path).exists() {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(file_cfg) = serde_yaml::from_str::<PartialConfig>(&content) {
-                    cfg.merge(file_cfg);
-                }
-            }
+        if Path::new(&path).exists()
+            && let Ok(content) = fs::read_to_string(&path)
+            && let Ok(file_cfg) = serde_yaml::from_str::<PartialConfig>(&content)
+        {
+            cfg.merge(file_cfg);
         }
     }
 
-    cfg.validate();
     let cli = Cli::parse();
     cfg.apply_cli(&cli);
+    cfg.validate();
 
     cfg
 });
@@ -99,6 +255,14 @@ struct PartialConfig {
     database: Option<PartialDatabaseConfig>,
     server: Option<PartialServerConfig>,
     jwt: Option<PartialJwtConfig>,
+    totp: Option<PartialTotpConfig>,
+    throttle: Option<PartialThrottleConfig>,
+    signup: Option<PartialSignupConfig>,
+    password: Option<PartialPasswordConfig>,
+    notifier: Option<PartialNotifierConfig>,
+    api_key: Option<PartialApiKeyConfig>,
+    oidc: Option<PartialOidcConfig>,
+    session_cookie: Option<PartialSessionCookieConfig>,
     log: Option<PartialLogConfig>,
 }
 
@@ -114,13 +278,85 @@ struct PartialServerConfig {
     cors: Option<Vec<String>>,
     #[serde(rename = "static")]
     static_dir: Option<String>,
+    trust_proxy: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 struct PartialJwtConfig {
     issuer: Option<String>,
     secret: Option<String>,
+    algorithm: Option<String>,
+    private_key: Option<String>,
+    kid: Option<String>,
+    keys: Option<Vec<JwtKeyConfig>>,
     expire: Option<i64>,
+    refresh_expire: Option<i64>,
+    cache_ttl: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialTotpConfig {
+    issuer: Option<String>,
+    challenge_expire: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialThrottleConfig {
+    free_attempts: Option<i64>,
+    backoff_max: Option<i64>,
+    lockout_threshold: Option<i64>,
+    lockout_duration: Option<i64>,
+    ip_lockout_threshold: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialSignupConfig {
+    conceal_existing: Option<bool>,
+    require_verification: Option<bool>,
+    verification_expire: Option<i64>,
+    resend_interval: Option<i64>,
+    verify_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialPasswordConfig {
+    reset_expire: Option<i64>,
+    hash_memory_kib: Option<u32>,
+    hash_iterations: Option<u32>,
+    hash_parallelism: Option<u32>,
+    min_length: Option<u64>,
+    min_entropy: Option<f64>,
+    deny_list: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialNotifierConfig {
+    kind: Option<String>,
+    file: Option<String>,
+    smtp_url: Option<String>,
+    from: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialApiKeyConfig {
+    default_expire: Option<i64>,
+    max_expire: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialOidcConfig {
+    issuer: Option<String>,
+    login_url: Option<String>,
+    code_expire: Option<i64>,
+    token_expire: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialSessionCookieConfig {
+    enabled: Option<bool>,
+    name: Option<String>,
+    secure: Option<bool>,
+    same_site: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -130,34 +366,36 @@ struct PartialLogConfig {
 
 impl Config {
     pub fn validate(&mut self) {
-        if let Some(ref level) = self.log.level {
-            if EnvFilter::try_new(level).is_err() {
-                eprintln!(
-                    "Invalid log filter expression: '{}'. Logging will be disabled.",
-                    level
-                );
-                self.log.level = None;
-            }
+        if let Some(ref level) = self.log.level
+            && EnvFilter::try_new(level).is_err()
+        {
+            eprintln!(
+                "Invalid log filter expression: '{}'. Logging will be disabled.",
+                level
+            );
+            self.log.level = None;
         }
 
-        if let Some(ref dir) = self.server.static_dir {
-            if !Path::new(dir).is_dir() {
-                eprintln!(
-                    "Static directory '{}' does not exist. Static serving will be disabled.",
-                    dir
-                );
-                self.server.static_dir = None;
-            }
+        if let Some(ref dir) = self.server.static_dir
+            && !Path::new(dir).is_dir()
+        {
+            eprintln!(
+                "Static directory '{}' does not exist. Static serving will be disabled.",
+                dir
+            );
+            self.server.static_dir = None;
         }
 
-        if let Some(ref file) = self.database.migration {
-            if !Path::new(file).is_file() {
-                eprintln!(
-                    "Migration file '{}' does not exist. Database migration will be disabled.",
-                    file
-                );
-                self.database.migration = None;
-            }
+        if !["Strict", "Lax", "None"].contains(&self.session_cookie.same_site.as_str()) {
+            eprintln!(
+                "Invalid SameSite policy: '{}'. Strict will be used.",
+                self.session_cookie.same_site
+            );
+            self.session_cookie.same_site = "Strict".to_string();
+        }
+        if self.session_cookie.same_site == "None" && !self.session_cookie.secure {
+            eprintln!("SameSite=None cookies must be Secure. Lax will be used.");
+            self.session_cookie.same_site = "Lax".to_string();
         }
     }
 
@@ -180,22 +418,158 @@ impl Config {
             if let Some(static_dir) = server.static_dir {
                 self.server.static_dir = Some(static_dir);
             }
+            if let Some(trust_proxy) = server.trust_proxy {
+                self.server.trust_proxy = trust_proxy;
+            }
         }
         if let Some(jwt) = p.jwt {
             if let Some(issuer) = jwt.issuer {
                 self.jwt.issuer = issuer;
             }
             if let Some(secret) = jwt.secret {
-                self.jwt.secret = secret;
+                self.jwt.secret = Some(secret);
+            }
+            if let Some(algorithm) = jwt.algorithm {
+                self.jwt.algorithm = algorithm;
+            }
+            if let Some(private_key) = jwt.private_key {
+                self.jwt.private_key = Some(private_key);
+            }
+            if let Some(kid) = jwt.kid {
+                self.jwt.kid = Some(kid);
+            }
+            if let Some(keys) = jwt.keys {
+                self.jwt.keys = keys;
             }
             if let Some(expire) = jwt.expire {
                 self.jwt.expire = expire;
             }
+            if let Some(refresh_expire) = jwt.refresh_expire {
+                self.jwt.refresh_expire = refresh_expire;
+            }
+            if let Some(cache_ttl) = jwt.cache_ttl {
+                self.jwt.cache_ttl = cache_ttl;
+            }
+        }
+        if let Some(totp) = p.totp {
+            if let Some(issuer) = totp.issuer {
+                self.totp.issuer = issuer;
+            }
+            if let Some(challenge_expire) = totp.challenge_expire {
+                self.totp.challenge_expire = challenge_expire;
+            }
+        }
+        if let Some(throttle) = p.throttle {
+            if let Some(free_attempts) = throttle.free_attempts {
+                self.throttle.free_attempts = free_attempts;
+            }
+            if let Some(backoff_max) = throttle.backoff_max {
+                self.throttle.backoff_max = backoff_max;
+            }
+            if let Some(lockout_threshold) = throttle.lockout_threshold {
+                self.throttle.lockout_threshold = lockout_threshold;
+            }
+            if let Some(lockout_duration) = throttle.lockout_duration {
+                self.throttle.lockout_duration = lockout_duration;
+            }
+            if let Some(ip_lockout_threshold) = throttle.ip_lockout_threshold {
+                self.throttle.ip_lockout_threshold = ip_lockout_threshold;
+            }
+        }
+        if let Some(signup) = p.signup {
+            if let Some(conceal_existing) = signup.conceal_existing {
+                self.signup.conceal_existing = conceal_existing;
+            }
+            if let Some(require_verification) = signup.require_verification {
+                self.signup.require_verification = require_verification;
+            }
+            if let Some(verification_expire) = signup.verification_expire {
+                self.signup.verification_expire = verification_expire;
+            }
+            if let Some(resend_interval) = signup.resend_interval {
+                self.signup.resend_interval = resend_interval;
+            }
+            if let Some(verify_url) = signup.verify_url {
+                self.signup.verify_url = Some(verify_url);
+            }
+        }
+        if let Some(password) = p.password {
+            if let Some(reset_expire) = password.reset_expire {
+                self.password.reset_expire = reset_expire;
+            }
+            if let Some(memory) = password.hash_memory_kib {
+                self.password.hash_memory_kib = memory;
+            }
+            if let Some(iterations) = password.hash_iterations {
+                self.password.hash_iterations = iterations;
+            }
+            if let Some(parallelism) = password.hash_parallelism {
+                self.password.hash_parallelism = parallelism;
+            }
+            if let Some(min_length) = password.min_length {
+                self.password.min_length = min_length;
+            }
+            if let Some(min_entropy) = password.min_entropy {
+                self.password.min_entropy = min_entropy;
+            }
+            if let Some(deny_list) = password.deny_list {
+                self.password.deny_list = Some(deny_list);
+            }
+        }
+        if let Some(notifier) = p.notifier {
+            if let Some(kind) = notifier.kind {
+                self.notifier.kind = kind;
+            }
+            if let Some(file) = notifier.file {
+                self.notifier.file = Some(file);
+            }
+            if let Some(smtp_url) = notifier.smtp_url {
+                self.notifier.smtp_url = Some(smtp_url);
+            }
+            if let Some(from) = notifier.from {
+                self.notifier.from = Some(from);
+            }
+        }
+        if let Some(api_key) = p.api_key {
+            if let Some(default_expire) = api_key.default_expire {
+                self.api_key.default_expire = default_expire;
+            }
+            if let Some(max_expire) = api_key.max_expire {
+                self.api_key.max_expire = max_expire;
+            }
+        }
+        if let Some(oidc) = p.oidc {
+            if let Some(issuer) = oidc.issuer {
+                self.oidc.issuer = Some(issuer);
+            }
+            if let Some(login_url) = oidc.login_url {
+                self.oidc.login_url = Some(login_url);
+            }
+            if let Some(code_expire) = oidc.code_expire {
+                self.oidc.code_expire = code_expire;
+            }
+            if let Some(token_expire) = oidc.token_expire {
+                self.oidc.token_expire = token_expire;
+            }
         }
-        if let Some(log) = p.log {
-            if let Some(level) = log.level {
-                self.log.level = Some(level);
+        if let Some(cookie) = p.session_cookie {
+            if let Some(enabled) = cookie.enabled {
+                self.session_cookie.enabled = enabled;
+            }
+            if let Some(name) = cookie.name {
+                self.session_cookie.name = name;
             }
+            if let Some(secure) = cookie.secure {
+                self.session_cookie.secure = secure;
+            }
+            if let Some(same_site) = cookie.same_site {
+                self.session_cookie.same_site = same_site;
+            }
+        }
+        if let Some(log) = p.log
+            && let Some(level) = log.level
+        {
+            self.log.level = Some(level);
         }
     }
 
@@ -212,6 +586,9 @@ impl Config {
                 eprintln!("Error: Invalid path string.");
             }
         }
+        if let Some(version) = cli.rollback {
+            self.database.rollback = Some(version);
+        }
         if let Some(host) = &cli.host {
             self.server.host = host.clone();
         }
@@ -229,15 +606,147 @@ impl Config {
                 eprintln!("Error: Invalid path string.");
             }
         }
+        if cli.trust_proxy {
+            self.server.trust_proxy = true;
+        }
         if let Some(issuer) = &cli.jwt_issuer {
             self.jwt.issuer = issuer.clone();
         }
         if let Some(secret) = &cli.jwt_secret {
-            self.jwt.secret = secret.clone();
+            self.jwt.secret = Some(secret.clone());
+        }
+        if let Some(algorithm) = &cli.jwt_algorithm {
+            self.jwt.algorithm = algorithm.clone();
+        }
+        if let Some(key) = &cli.jwt_private_key {
+            if let Some(key_str) = key.to_str() {
+                self.jwt.private_key = Some(key_str.to_string());
+            } else {
+                eprintln!("Error: Invalid path string.");
+            }
+        }
+        if let Some(kid) = &cli.jwt_kid {
+            self.jwt.kid = Some(kid.clone());
         }
         if let Some(exp) = cli.jwt_expire {
             self.jwt.expire = exp;
         }
+        if let Some(exp) = cli.jwt_refresh_expire {
+            self.jwt.refresh_expire = exp;
+        }
+        if let Some(ttl) = cli.jwt_cache_ttl {
+            self.jwt.cache_ttl = ttl;
+        }
+        if let Some(issuer) = &cli.totp_issuer {
+            self.totp.issuer = issuer.clone();
+        }
+        if let Some(exp) = cli.totp_challenge_expire {
+            self.totp.challenge_expire = exp;
+        }
+        if let Some(n) = cli.throttle_free_attempts {
+            self.throttle.free_attempts = n;
+        }
+        if let Some(secs) = cli.throttle_backoff_max {
+            self.throttle.backoff_max = secs;
+        }
+        if let Some(n) = cli.throttle_lockout_threshold {
+            self.throttle.lockout_threshold = n;
+        }
+        if let Some(secs) = cli.throttle_lockout_duration {
+            self.throttle.lockout_duration = secs;
+        }
+        if let Some(n) = cli.throttle_ip_lockout_threshold {
+            self.throttle.ip_lockout_threshold = n;
+        }
+        if cli.signup_conceal_existing {
+            self.signup.conceal_existing = true;
+        }
+        if cli.signup_require_verification {
+            self.signup.require_verification = true;
+        }
+        if let Some(exp) = cli.signup_verification_expire {
+            self.signup.verification_expire = exp;
+        }
+        if let Some(secs) = cli.signup_resend_interval {
+            self.signup.resend_interval = secs;
+        }
+        if let Some(url) = &cli.signup_verify_url {
+            self.signup.verify_url = Some(url.clone());
+        }
+        if let Some(exp) = cli.password_reset_expire {
+            self.password.reset_expire = exp;
+        }
+        if let Some(memory) = cli.password_hash_memory_kib {
+            self.password.hash_memory_kib = memory;
+        }
+        if let Some(iterations) = cli.password_hash_iterations {
+            self.password.hash_iterations = iterations;
+        }
+        if let Some(parallelism) = cli.password_hash_parallelism {
+            self.password.hash_parallelism = parallelism;
+        }
+        if let Some(min_length) = cli.password_min_length {
+            self.password.min_length = min_length;
+        }
+        if let Some(min_entropy) = cli.password_min_entropy {
+            self.password.min_entropy = min_entropy;
+        }
+        if let Some(file) = &cli.password_deny_list {
+            if let Some(file_str) = file.to_str() {
+                self.password.deny_list = Some(file_str.to_string());
+            } else {
+                eprintln!("Error: Invalid path string.");
+            }
+        }
+        if let Some(kind) = &cli.notifier {
+            self.notifier.kind = kind.clone();
+        }
+        if let Some(file) = &cli.notifier_file {
+            if let Some(file_str) = file.to_str() {
+                self.notifier.file = Some(file_str.to_string());
+            } else {
+                eprintln!("Error: Invalid path string.");
+            }
+        }
+        if let Some(url) = &cli.notifier_smtp_url {
+            self.notifier.smtp_url = Some(url.clone());
+        }
+        if let Some(from) = &cli.notifier_from {
+            self.notifier.from = Some(from.clone());
+        }
+        if let Some(exp) = cli.api_key_default_expire {
+            self.api_key.default_expire = exp;
+        }
+        if let Some(exp) = cli.api_key_max_expire {
+            self.api_key.max_expire = exp;
+        }
+        if let Some(issuer) = &cli.oidc_issuer {
+            self.oidc.issuer = Some(issuer.clone());
+        }
+        if let Some(login_url) = &cli.oidc_login_url {
+            self.oidc.login_url = Some(login_url.clone());
+        }
+        if let Some(exp) = cli.oidc_code_expire {
+            self.oidc.code_expire = exp;
+        }
+        if let Some(exp) = cli.oidc_token_expire {
+            self.oidc.token_expire = exp;
+        }
+        if cli.session_cookie {
+            self.session_cookie.enabled = true;
+        }
+        if let Some(name) = &cli.session_cookie_name {
+            self.session_cookie.name = name.clone();
+        }
+        if cli.session_cookie_insecure {
+            self.session_cookie.secure = false;
+        }
+        if let Some(same_site) = &cli.session_cookie_same_site {
+            self.session_cookie.same_site = same_site.clone();
+        }
+        if let Some(account) = &cli.grant_admin {
+            self.grant_admin = Some(account.clone());
+        }
         if cli.no_log {
             self.log.level = None;
         } else if let Some(level) = &cli.log_level {
@@ -262,6 +771,8 @@ pub struct Cli {
     pub migration: Option<PathBuf>,
     #[arg(long)]
     pub no_migration: bool,
+    #[arg(long, value_name = "VERSION")]
+    pub rollback: Option<i64>,
 
     #[arg(long)]
     pub host: Option<String>,
@@ -274,13 +785,102 @@ pub struct Cli {
     pub static_dir: Option<PathBuf>,
     #[arg(long)]
     pub no_static: bool,
+    #[arg(long)]
+    pub trust_proxy: bool,
 
     #[arg(long)]
     pub jwt_issuer: Option<String>,
     #[arg(long)]
     pub jwt_secret: Option<String>,
     #[arg(long)]
+    pub jwt_algorithm: Option<String>,
+    #[arg(long)]
+    pub jwt_private_key: Option<PathBuf>,
+    #[arg(long)]
+    pub jwt_kid: Option<String>,
+    #[arg(long)]
     pub jwt_expire: Option<i64>,
+    #[arg(long)]
+    pub jwt_refresh_expire: Option<i64>,
+    #[arg(long)]
+    pub jwt_cache_ttl: Option<u64>,
+
+    #[arg(long)]
+    pub totp_issuer: Option<String>,
+    #[arg(long)]
+    pub totp_challenge_expire: Option<i64>,
+
+    #[arg(long)]
+    pub throttle_free_attempts: Option<i64>,
+    #[arg(long)]
+    pub throttle_backoff_max: Option<i64>,
+    #[arg(long)]
+    pub throttle_lockout_threshold: Option<i64>,
+    #[arg(long)]
+    pub throttle_lockout_duration: Option<i64>,
+    #[arg(long)]
+    pub throttle_ip_lockout_threshold: Option<i64>,
+
+    #[arg(long)]
+    pub signup_conceal_existing: bool,
+    #[arg(long)]
+    pub signup_require_verification: bool,
+    #[arg(long)]
+    pub signup_verification_expire: Option<i64>,
+    #[arg(long)]
+    pub signup_resend_interval: Option<i64>,
+    #[arg(long, value_name = "URL")]
+    pub signup_verify_url: Option<String>,
+
+    #[arg(long)]
+    pub password_reset_expire: Option<i64>,
+    #[arg(long)]
+    pub password_hash_memory_kib: Option<u32>,
+    #[arg(long)]
+    pub password_hash_iterations: Option<u32>,
+    #[arg(long)]
+    pub password_hash_parallelism: Option<u32>,
+    #[arg(long)]
+    pub password_min_length: Option<u64>,
+    #[arg(long)]
+    pub password_min_entropy: Option<f64>,
+    #[arg(long)]
+    pub password_deny_list: Option<PathBuf>,
+
+    #[arg(long, value_name = "KIND")]
+    pub notifier: Option<String>,
+    #[arg(long)]
+    pub notifier_file: Option<PathBuf>,
+    #[arg(long, value_name = "URL")]
+    pub notifier_smtp_url: Option<String>,
+    #[arg(long, value_name = "ADDRESS")]
+    pub notifier_from: Option<String>,
+
+    #[arg(long)]
+    pub api_key_default_expire: Option<i64>,
+    #[arg(long)]
+    pub api_key_max_expire: Option<i64>,
+
+    #[arg(long, value_name = "URL")]
+    pub oidc_issuer: Option<String>,
+    #[arg(long, value_name = "URL")]
+    pub oidc_login_url: Option<String>,
+    #[arg(long)]
+    pub oidc_code_expire: Option<i64>,
+    #[arg(long)]
+    pub oidc_token_expire: Option<i64>,
+
+    #[arg(long)]
+    pub session_cookie: bool,
+    #[arg(long)]
+    pub session_cookie_name: Option<String>,
+    #[arg(long)]
+    pub session_cookie_insecure: bool,
+    #[arg(long, value_name = "POLICY")]
+    pub session_cookie_same_site: Option<String>,
+
+    #[arg(long, value_name = "ACCOUNT")]
+    pub grant_admin: Option<String>,
 
     #[arg(long)]
     pub log_level: Option<String>,
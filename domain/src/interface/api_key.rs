@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+
+use crate::model::api_key::ApiKeyEntity;
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn insert(&mut self, entity: &ApiKeyEntity) -> Result<(), BoxError>;
+    async fn select_by_hash(&mut self, key_hash: &str) -> Result<Option<ApiKeyEntity>, BoxError>;
+    async fn select_by_account(&mut self, account: &str) -> Result<Vec<ApiKeyEntity>, BoxError>;
+    async fn touch(
+        &mut self,
+        id: &str,
+        now: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<(), BoxError>;
+    async fn delete(&mut self, account: &str, id: &str) -> Result<bool, BoxError>;
+    async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError>;
+    async fn delete_expired(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError>;
+}
@@ -0,0 +1,6 @@
+use chrono::{DateTime, Utc};
+
+// Source of the current time for use cases that schedule against it.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+
+use crate::model::login_attempt::LoginAttemptEntity;
+
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync {
+    async fn select(
+        &mut self,
+        scope: &str,
+        subject: &str,
+    ) -> Result<Option<LoginAttemptEntity>, BoxError>;
+    // Counts a failure atomically, restarting from 1 when the previous one is older than `reset_before`.
+    async fn record_failure(
+        &mut self,
+        scope: &str,
+        subject: &str,
+        now: DateTime<Utc>,
+        reset_before: DateTime<Utc>,
+    ) -> Result<LoginAttemptEntity, BoxError>;
+    async fn block(
+        &mut self,
+        scope: &str,
+        subject: &str,
+        until: DateTime<Utc>,
+    ) -> Result<(), BoxError>;
+    async fn delete(&mut self, scope: &str, subject: &str) -> Result<bool, BoxError>;
+    async fn delete_stale(&mut self, before: DateTime<Utc>) -> Result<u64, BoxError>;
+}
@@ -1,10 +1,34 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use common::types::BoxError;
 
-use crate::model::member::MemberEntity;
+use crate::model::member::{MemberEntity, Role};
 
 #[async_trait]
 pub trait MemberRepository: Send + Sync {
     async fn insert(&mut self, member: &MemberEntity) -> Result<MemberEntity, BoxError>;
     async fn select(&mut self, accunt: &str) -> Result<Option<MemberEntity>, BoxError>;
+    async fn select_page(&mut self, offset: i64, limit: i64)
+    -> Result<Vec<MemberEntity>, BoxError>;
+    async fn update_disabled(&mut self, account: &str, disabled: bool) -> Result<bool, BoxError>;
+    async fn update_password(&mut self, account: &str, password: &str) -> Result<bool, BoxError>;
+    // Only succeeds while `email` is still the address on the account.
+    async fn update_verified(&mut self, account: &str, email: &str) -> Result<bool, BoxError>;
+    // Records a verification mail sent at `at`, unless the account is verified or the last
+    // one was sent after `since`; false means no mail should be sent.
+    async fn update_verification_sent(
+        &mut self,
+        account: &str,
+        at: DateTime<Utc>,
+        since: DateTime<Utc>,
+    ) -> Result<bool, BoxError>;
+    async fn update_timezone(
+        &mut self,
+        account: &str,
+        timezone: Option<&str>,
+    ) -> Result<bool, BoxError>;
+    async fn delete(&mut self, account: &str) -> Result<bool, BoxError>;
+    async fn select_roles(&mut self, account: &str) -> Result<Vec<Role>, BoxError>;
+    async fn insert_role(&mut self, account: &str, role: Role) -> Result<(), BoxError>;
+    async fn delete_roles(&mut self, account: &str) -> Result<u64, BoxError>;
 }
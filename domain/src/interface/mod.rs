@@ -1,2 +1,12 @@
-pub mod todo;
+pub mod api_key;
+pub mod clock;
+pub mod login_attempt;
 pub mod member;
+pub mod notifier;
+pub mod oidc;
+pub mod password_reset;
+pub mod refresh_token;
+pub mod revoked_token;
+pub mod tag;
+pub mod todo;
+pub mod two_factor;
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use common::types::BoxError;
+
+use crate::model::notification::Notification;
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, notification: &Notification) -> Result<(), BoxError>;
+}
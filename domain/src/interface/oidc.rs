@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+
+use crate::model::oidc::{AuthorizationCodeEntity, OidcClientEntity};
+
+#[async_trait]
+pub trait OidcRepository: Send + Sync {
+    async fn insert_client(&mut self, entity: &OidcClientEntity) -> Result<(), BoxError>;
+    async fn select_client(
+        &mut self,
+        client_id: &str,
+    ) -> Result<Option<OidcClientEntity>, BoxError>;
+    async fn select_clients(&mut self) -> Result<Vec<OidcClientEntity>, BoxError>;
+    async fn delete_client(&mut self, client_id: &str) -> Result<bool, BoxError>;
+
+    async fn insert_code(&mut self, entity: &AuthorizationCodeEntity) -> Result<(), BoxError>;
+    // Deletes and returns the code, so it can be exchanged only once.
+    async fn take_code(
+        &mut self,
+        code_hash: &str,
+    ) -> Result<Option<AuthorizationCodeEntity>, BoxError>;
+    async fn delete_codes_by_account(&mut self, account: &str) -> Result<u64, BoxError>;
+    async fn delete_codes_by_client(&mut self, client_id: &str) -> Result<u64, BoxError>;
+    async fn delete_expired_codes(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError>;
+}
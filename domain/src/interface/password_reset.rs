@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+
+use crate::model::password_reset::PasswordResetEntity;
+
+#[async_trait]
+pub trait PasswordResetRepository: Send + Sync {
+    async fn insert(&mut self, entity: &PasswordResetEntity) -> Result<(), BoxError>;
+    async fn select(&mut self, token_hash: &str) -> Result<Option<PasswordResetEntity>, BoxError>;
+    async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError>;
+    async fn delete_expired(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError>;
+}
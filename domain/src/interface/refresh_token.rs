@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use common::types::BoxError;
+
+use crate::model::refresh_token::RefreshTokenEntity;
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn insert(&mut self, entity: &RefreshTokenEntity)
+    -> Result<RefreshTokenEntity, BoxError>;
+    async fn select(&mut self, token_hash: &str) -> Result<Option<RefreshTokenEntity>, BoxError>;
+    async fn mark_used(&mut self, token_hash: &str) -> Result<bool, BoxError>;
+    async fn revoke_family(&mut self, family: &str) -> Result<u64, BoxError>;
+    async fn revoke_account(&mut self, account: &str) -> Result<u64, BoxError>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+
+use crate::model::revoked_token::RevokedTokenEntity;
+
+#[async_trait]
+pub trait RevokedTokenRepository: Send + Sync {
+    async fn insert(&mut self, entity: &RevokedTokenEntity) -> Result<(), BoxError>;
+    async fn exists(&mut self, jti: &str) -> Result<bool, BoxError>;
+    async fn delete_expired(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError>;
+    async fn revoke_account(&mut self, account: &str, at: DateTime<Utc>) -> Result<(), BoxError>;
+    async fn select_account_revoked_at(
+        &mut self,
+        account: &str,
+    ) -> Result<Option<DateTime<Utc>>, BoxError>;
+}
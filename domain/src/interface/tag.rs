@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use common::types::BoxError;
+
+use crate::model::tag::{TagEntity, TodoTagEntity};
+
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    async fn insert(&mut self, entity: &TagEntity) -> Result<TagEntity, BoxError>;
+    async fn select(&mut self, account: &str, id: i64) -> Result<Option<TagEntity>, BoxError>;
+    async fn select_by_account(&mut self, account: &str) -> Result<Vec<TagEntity>, BoxError>;
+    async fn select_by_names(
+        &mut self,
+        account: &str,
+        names: &[String],
+    ) -> Result<Vec<TagEntity>, BoxError>;
+    async fn update(&mut self, entity: &TagEntity) -> Result<Option<TagEntity>, BoxError>;
+    async fn delete(&mut self, account: &str, id: i64) -> Result<Option<TagEntity>, BoxError>;
+    async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError>;
+    async fn select_by_todos(&mut self, todo_ids: &[i64]) -> Result<Vec<TodoTagEntity>, BoxError>;
+    async fn assign(&mut self, todo_id: i64, tag_id: i64) -> Result<(), BoxError>;
+    async fn unassign(&mut self, todo_id: i64, tag_id: i64) -> Result<bool, BoxError>;
+    async fn unassign_by_todo(&mut self, todo_id: i64) -> Result<u64, BoxError>;
+}
@@ -1,10 +1,27 @@
 use async_trait::async_trait;
 use common::types::BoxError;
 
-use crate::model::todo::TodoEntity;
+use crate::model::todo::{ChecklistItemEntity, TodoEntity, TodoQuery};
 
 #[async_trait]
 pub trait TodoRepository: Send + Sync {
     async fn insert(&mut self, entity: &TodoEntity) -> Result<TodoEntity, BoxError>;
     async fn selectl(&mut self, id: i64) -> Result<Option<TodoEntity>, BoxError>;
+    async fn select_page(&mut self, query: &TodoQuery) -> Result<Vec<TodoEntity>, BoxError>;
+    async fn select_by_parents(&mut self, parent_ids: &[i64]) -> Result<Vec<TodoEntity>, BoxError>;
+    async fn update(&mut self, entity: &TodoEntity) -> Result<Option<TodoEntity>, BoxError>;
+    async fn delete(&mut self, id: i64) -> Result<Option<TodoEntity>, BoxError>;
+    async fn detach_children(&mut self, parent_id: i64) -> Result<u64, BoxError>;
+    async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError>;
+}
+
+#[async_trait]
+pub trait ChecklistRepository: Send + Sync {
+    async fn insert(&mut self, entity: &ChecklistItemEntity) -> Result<(), BoxError>;
+    async fn select_by_todos(
+        &mut self,
+        todo_ids: &[i64],
+    ) -> Result<Vec<ChecklistItemEntity>, BoxError>;
+    async fn delete_by_todo(&mut self, todo_id: i64) -> Result<u64, BoxError>;
+    async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError>;
 }
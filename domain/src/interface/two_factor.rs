@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+
+use crate::model::two_factor::{RecoveryCodeEntity, SigninChallengeEntity, TotpEntity};
+
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync {
+    async fn select_totp(&mut self, account: &str) -> Result<Option<TotpEntity>, BoxError>;
+    async fn upsert_totp(&mut self, entity: &TotpEntity) -> Result<(), BoxError>;
+    async fn confirm_totp(&mut self, account: &str, step: i64) -> Result<bool, BoxError>;
+    async fn advance_totp_step(&mut self, account: &str, step: i64) -> Result<bool, BoxError>;
+    async fn delete_totp(&mut self, account: &str) -> Result<bool, BoxError>;
+
+    async fn insert_recovery_code(&mut self, entity: &RecoveryCodeEntity) -> Result<(), BoxError>;
+    async fn select_unused_recovery_codes(
+        &mut self,
+        account: &str,
+    ) -> Result<Vec<RecoveryCodeEntity>, BoxError>;
+    async fn use_recovery_code(&mut self, account: &str, code_hash: &str)
+    -> Result<bool, BoxError>;
+    async fn delete_recovery_codes(&mut self, account: &str) -> Result<u64, BoxError>;
+
+    async fn insert_challenge(&mut self, entity: &SigninChallengeEntity) -> Result<(), BoxError>;
+    async fn select_challenge(
+        &mut self,
+        token_hash: &str,
+    ) -> Result<Option<SigninChallengeEntity>, BoxError>;
+    async fn increment_challenge_attempts(&mut self, token_hash: &str) -> Result<(), BoxError>;
+    async fn delete_challenge(&mut self, token_hash: &str) -> Result<bool, BoxError>;
+    async fn delete_expired_challenges(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError>;
+}
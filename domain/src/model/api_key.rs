@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::{fmt, str::FromStr};
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKeyEntity {
+    pub id: String,
+    pub account: String,
+    pub name: String,
+    pub key_hash: String,
+    pub scopes: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKeyEntity {
+    // Stored space-separated; unknown names left by older versions are ignored.
+    pub fn scope_list(&self) -> Vec<Scope> {
+        self.scopes
+            .split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    #[serde(rename = "todo:read")]
+    TodoRead,
+    #[serde(rename = "todo:write")]
+    TodoWrite,
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::TodoRead => "todo:read",
+            Scope::TodoWrite => "todo:write",
+            Scope::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "todo:read" => Ok(Scope::TodoRead),
+            "todo:write" => Ok(Scope::TodoWrite),
+            "admin" => Ok(Scope::Admin),
+            _ => Err(format!("unknown scope: {s}")),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+pub const SCOPE_ACCOUNT: &str = "account";
+pub const SCOPE_IP: &str = "ip";
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct LoginAttemptEntity {
+    pub scope: String,
+    pub subject: String,
+    pub failures: i64,
+    pub blocked_until: Option<DateTime<Utc>>,
+    pub last_failed_at: DateTime<Utc>,
+}
@@ -1,8 +1,51 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::{fmt, str::FromStr};
 
 #[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
 pub struct MemberEntity {
     pub account: String,
     pub password: String,
+    pub disabled: bool,
+    pub email: Option<String>,
+    // False while a signup waits for its email address to be verified
+    pub verified: bool,
+    pub verification_sent_at: Option<DateTime<Utc>>,
+    // IANA name such as `Asia/Tokyo`; recurring todos are scheduled in UTC without it
+    pub timezone: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum Role {
+    Member,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Member => "member",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "member" => Ok(Role::Member),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("unknown role: {s}")),
+        }
+    }
 }
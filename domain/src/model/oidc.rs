@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct OidcClientEntity {
+    pub client_id: String,
+    pub name: String,
+    // None for public clients, which authenticate with PKCE alone.
+    pub secret_hash: Option<String>,
+    pub redirect_uris: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OidcClientEntity {
+    // Stored space-separated; registered URIs never contain whitespace.
+    pub fn redirect_uri_list(&self) -> Vec<String> {
+        self.redirect_uris
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct AuthorizationCodeEntity {
+    pub code_hash: String,
+    pub client_id: String,
+    pub account: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub nonce: Option<String>,
+    pub code_challenge: String,
+    pub expires_at: DateTime<Utc>,
+}
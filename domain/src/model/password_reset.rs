@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct PasswordResetEntity {
+    pub token_hash: String,
+    pub account: String,
+    pub expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,534 @@
+use chrono::{
+    DateTime, Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::{fmt, str::FromStr};
+
+const MAX_INTERVAL: u32 = 999;
+// Upper bound on occurrences skipped while catching up with the clock.
+const MAX_STEPS: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+// The RFC 5545 RRULE subset supported for todos: FREQ, INTERVAL, BYDAY (weekly, without
+// ordinals), BYMONTHDAY (monthly), COUNT and UNTIL. Weeks start on Monday.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Vec<i32>,
+    // Occurrences left in the series, counting the current one
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Occurrence {
+    pub due_date: DateTime<Utc>,
+    // The rule as seen from this occurrence
+    pub recurrence: Recurrence,
+}
+
+impl Recurrence {
+    pub fn new(frequency: Frequency) -> Self {
+        Self {
+            frequency,
+            interval: 1,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            count: None,
+            until: None,
+        }
+    }
+
+    // The first occurrence after `due_date` that is later than `now`. Dates are stepped on
+    // the wall clock of `tz`, so a todo due at 09:00 stays at 09:00 local time across DST
+    // changes; occurrences missed while overdue are skipped and still use up COUNT.
+    pub fn next_occurrence(
+        &self,
+        due_date: DateTime<Utc>,
+        tz: Tz,
+        now: DateTime<Utc>,
+    ) -> Option<Occurrence> {
+        let local = due_date.with_timezone(&tz).naive_local();
+        let time = local.time();
+        let mut date = local.date();
+        let mut recurrence = self.clone();
+        for _ in 0..MAX_STEPS {
+            if recurrence.count.is_some_and(|c| c <= 1) {
+                return None;
+            }
+            date = recurrence.next_date(date)?;
+            recurrence.count = recurrence.count.map(|c| c - 1);
+            let due_date = to_utc(tz, date.and_time(time))?;
+            if recurrence.until.is_some_and(|until| due_date > until) {
+                return None;
+            }
+            if due_date > now {
+                return Some(Occurrence {
+                    due_date,
+                    recurrence,
+                });
+            }
+        }
+        None
+    }
+
+    fn next_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let interval = self.interval.max(1);
+        match self.frequency {
+            Frequency::Daily => date.checked_add_days(Days::new(interval as u64)),
+            Frequency::Weekly if self.by_day.is_empty() => {
+                date.checked_add_days(Days::new(7 * interval as u64))
+            }
+            Frequency::Weekly => {
+                let weekday = date.weekday().num_days_from_monday();
+                let mut days: Vec<u32> = self
+                    .by_day
+                    .iter()
+                    .map(|d| d.num_days_from_monday())
+                    .collect();
+                days.sort_unstable();
+                match days.iter().find(|d| **d > weekday) {
+                    Some(d) => date.checked_add_days(Days::new((d - weekday) as u64)),
+                    None => {
+                        date.checked_add_days(Days::new((7 * interval + days[0] - weekday) as u64))
+                    }
+                }
+            }
+            Frequency::Monthly => {
+                let month_days = if self.by_month_day.is_empty() {
+                    vec![date.day() as i32]
+                } else {
+                    self.by_month_day.clone()
+                };
+                if let Some(day) = days_in_month(date.year(), date.month(), &month_days)
+                    .into_iter()
+                    .find(|d| *d > date.day())
+                {
+                    return date.with_day(day);
+                }
+                // Months without a matching day (the 31st in April) are skipped, not clamped.
+                let mut month = date.year() * 12 + date.month0() as i32;
+                for _ in 0..MAX_STEPS {
+                    month += interval as i32;
+                    let (year, month) = (month.div_euclid(12), month.rem_euclid(12) as u32 + 1);
+                    if let Some(day) = days_in_month(year, month, &month_days).first() {
+                        return NaiveDate::from_ymd_opt(year, month, *day);
+                    }
+                }
+                None
+            }
+            Frequency::Yearly => {
+                let mut year = date.year();
+                for _ in 0..MAX_STEPS {
+                    year = year.checked_add(interval as i32)?;
+                    if let Some(next) = NaiveDate::from_ymd_opt(year, date.month(), date.day()) {
+                        return Some(next);
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+// Days of the month matching BYMONTHDAY values, where -1 is the last day.
+fn days_in_month(year: i32, month: u32, month_days: &[i32]) -> Vec<u32> {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return Vec::new();
+    };
+    let length = first
+        .checked_add_months(chrono::Months::new(1))
+        .map_or(31, |next| {
+            next.signed_duration_since(first).num_days() as i32
+        });
+    let mut days: Vec<u32> = month_days
+        .iter()
+        .map(|d| if *d < 0 { length + d + 1 } else { *d })
+        .filter(|d| (1..=length).contains(d))
+        .map(|d| d as u32)
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+// A wall-clock time skipped by a DST change is moved forward by the size of the gap.
+fn to_utc(tz: Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + TimeDelta::hours(1)))
+                .earliest()
+        })
+        .map(|t| t.with_timezone(&Utc))
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.frequency.as_str())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(|d| d.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+// Accepts `daily`, `weekly`, `monthly` and `yearly` as shorthands for a bare FREQ.
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(frequency) = parse_frequency(s) {
+            return Ok(Recurrence::new(frequency));
+        }
+        let rule = match s.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &s[6..],
+            _ => s,
+        };
+
+        let mut frequency = None;
+        let mut recurrence = Recurrence::new(Frequency::Daily);
+        let mut seen = Vec::new();
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("invalid RRULE part: {part}"))?;
+            let key = key.trim().to_ascii_uppercase();
+            let value = value.trim();
+            if seen.contains(&key) {
+                return Err(format!("duplicate RRULE part: {key}"));
+            }
+            match key.as_str() {
+                "FREQ" => frequency = Some(parse_frequency(value)?),
+                "INTERVAL" => {
+                    recurrence.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|i| (1..=MAX_INTERVAL).contains(i))
+                        .ok_or_else(|| format!("INTERVAL must be 1 to {MAX_INTERVAL}"))?;
+                }
+                "BYDAY" => {
+                    recurrence.by_day = value
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Result<_, _>>()?;
+                    recurrence.by_day.sort_by_key(|d| d.num_days_from_monday());
+                    recurrence.by_day.dedup();
+                }
+                "BYMONTHDAY" => {
+                    recurrence.by_month_day = value
+                        .split(',')
+                        .map(|d| {
+                            d.trim()
+                                .parse::<i32>()
+                                .ok()
+                                .filter(|d| (1..=31).contains(&d.abs()))
+                                .ok_or_else(|| format!("invalid BYMONTHDAY: {d}"))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "COUNT" => {
+                    recurrence.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|c| *c >= 1)
+                            .ok_or_else(|| "COUNT must be a positive number".to_string())?,
+                    );
+                }
+                "UNTIL" => recurrence.until = Some(parse_until(value)?),
+                _ => return Err(format!("unsupported RRULE part: {key}")),
+            }
+            seen.push(key);
+        }
+
+        recurrence.frequency = frequency.ok_or_else(|| "FREQ is required".to_string())?;
+        if !recurrence.by_day.is_empty() && recurrence.frequency != Frequency::Weekly {
+            return Err("BYDAY is only supported with FREQ=WEEKLY".to_string());
+        }
+        if !recurrence.by_month_day.is_empty() && recurrence.frequency != Frequency::Monthly {
+            return Err("BYMONTHDAY is only supported with FREQ=MONTHLY".to_string());
+        }
+        if recurrence.count.is_some() && recurrence.until.is_some() {
+            return Err("COUNT and UNTIL cannot be combined".to_string());
+        }
+        Ok(recurrence)
+    }
+}
+
+fn parse_frequency(s: &str) -> Result<Frequency, String> {
+    match s.to_ascii_uppercase().as_str() {
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        "YEARLY" => Ok(Frequency::Yearly),
+        _ => Err(format!("unsupported FREQ: {s}")),
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+    match s.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(format!("invalid BYDAY: {s}")),
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+// A bare date means the end of that day in UTC.
+fn parse_until(s: &str) -> Result<DateTime<Utc>, String> {
+    let invalid = || format!("invalid UNTIL: {s}");
+    if let Some(s) = s.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(s, "%Y%m%dT%H%M%S")
+            .map(|t| t.and_utc())
+            .map_err(|_| invalid());
+    }
+    NaiveDate::parse_from_str(s, "%Y%m%d")
+        .map(|d| {
+            d.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default())
+                .and_utc()
+        })
+        .map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(rule: &str, due: &str, tz: Tz, now: &str) -> Option<(DateTime<Utc>, String)> {
+        let recurrence: Recurrence = rule.parse().unwrap();
+        recurrence
+            .next_occurrence(utc(due), tz, utc(now))
+            .map(|o| (o.due_date, o.recurrence.to_string()))
+    }
+
+    #[test]
+    fn parses_shorthands_and_rrules_into_canonical_form() {
+        assert_eq!(
+            "daily".parse::<Recurrence>().unwrap().to_string(),
+            "FREQ=DAILY"
+        );
+        assert_eq!(
+            "Weekly".parse::<Recurrence>().unwrap().to_string(),
+            "FREQ=WEEKLY"
+        );
+        assert_eq!(
+            "RRULE:freq=weekly;byday=we,mo,WE;interval=2"
+                .parse::<Recurrence>()
+                .unwrap()
+                .to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE"
+        );
+        assert_eq!(
+            "FREQ=MONTHLY;BYMONTHDAY=1,-1;UNTIL=20240630"
+                .parse::<Recurrence>()
+                .unwrap()
+                .to_string(),
+            "FREQ=MONTHLY;BYMONTHDAY=1,-1;UNTIL=20240630T235959Z"
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        for rule in [
+            "",
+            "hourly",
+            "INTERVAL=2",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20240101",
+            "FREQ=DAILY;FREQ=WEEKLY",
+            "FREQ=DAILY;BYSETPOS=1",
+        ] {
+            assert!(rule.parse::<Recurrence>().is_err(), "{rule}");
+        }
+    }
+
+    #[test]
+    fn steps_daily_and_weekly() {
+        let now = "2024-01-01T00:00:00Z";
+        assert_eq!(
+            next(
+                "FREQ=DAILY;INTERVAL=3",
+                "2024-01-30T09:00:00Z",
+                Tz::UTC,
+                now
+            )
+            .unwrap()
+            .0,
+            utc("2024-02-02T09:00:00Z")
+        );
+        assert_eq!(
+            next("weekly", "2024-01-03T09:00:00Z", Tz::UTC, now)
+                .unwrap()
+                .0,
+            utc("2024-01-10T09:00:00Z")
+        );
+        // Wednesday 2024-01-03 -> Friday of the same week -> Monday two weeks later
+        let rule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR";
+        assert_eq!(
+            next(rule, "2024-01-03T09:00:00Z", Tz::UTC, now).unwrap().0,
+            utc("2024-01-05T09:00:00Z")
+        );
+        assert_eq!(
+            next(rule, "2024-01-05T09:00:00Z", Tz::UTC, now).unwrap().0,
+            utc("2024-01-15T09:00:00Z")
+        );
+    }
+
+    #[test]
+    fn skips_months_and_years_without_the_day() {
+        let now = "2024-01-01T00:00:00Z";
+        assert_eq!(
+            next("monthly", "2024-01-31T09:00:00Z", Tz::UTC, now)
+                .unwrap()
+                .0,
+            utc("2024-03-31T09:00:00Z")
+        );
+        let rule = "FREQ=MONTHLY;BYMONTHDAY=15,-1";
+        assert_eq!(
+            next(rule, "2024-02-15T09:00:00Z", Tz::UTC, now).unwrap().0,
+            utc("2024-02-29T09:00:00Z")
+        );
+        assert_eq!(
+            next(rule, "2024-02-29T09:00:00Z", Tz::UTC, now).unwrap().0,
+            utc("2024-03-15T09:00:00Z")
+        );
+        assert_eq!(
+            next("yearly", "2024-02-29T09:00:00Z", Tz::UTC, now)
+                .unwrap()
+                .0,
+            utc("2028-02-29T09:00:00Z")
+        );
+    }
+
+    #[test]
+    fn keeps_the_local_time_across_dst() {
+        // 09:00 in New York is 14:00 UTC in winter and 13:00 UTC in summer.
+        let now = "2024-01-01T00:00:00Z";
+        assert_eq!(
+            next("daily", "2024-03-09T14:00:00Z", Tz::America__New_York, now)
+                .unwrap()
+                .0,
+            utc("2024-03-10T13:00:00Z")
+        );
+        // 02:30 does not exist on 2024-03-10 and moves to 03:30 EDT.
+        assert_eq!(
+            next("daily", "2024-03-09T07:30:00Z", Tz::America__New_York, now)
+                .unwrap()
+                .0,
+            utc("2024-03-10T07:30:00Z")
+        );
+        // In UTC the instant simply moves by 24 hours.
+        assert_eq!(
+            next("daily", "2024-03-09T14:00:00Z", Tz::UTC, now)
+                .unwrap()
+                .0,
+            utc("2024-03-10T14:00:00Z")
+        );
+    }
+
+    #[test]
+    fn counts_down_and_stops_at_until() {
+        let now = "2024-01-01T00:00:00Z";
+        assert_eq!(
+            next("FREQ=DAILY;COUNT=3", "2024-01-01T09:00:00Z", Tz::UTC, now),
+            Some((
+                utc("2024-01-02T09:00:00Z"),
+                "FREQ=DAILY;COUNT=2".to_string()
+            ))
+        );
+        assert_eq!(
+            next("FREQ=DAILY;COUNT=1", "2024-01-01T09:00:00Z", Tz::UTC, now),
+            None
+        );
+        let rule = "FREQ=DAILY;UNTIL=20240102T090000Z";
+        assert!(next(rule, "2024-01-01T09:00:00Z", Tz::UTC, now).is_some());
+        assert_eq!(next(rule, "2024-01-02T09:00:00Z", Tz::UTC, now), None);
+    }
+
+    #[test]
+    fn skips_occurrences_already_in_the_past() {
+        // Completed on 2024-01-10 12:00, a daily todo due on the 1st comes back on the 11th.
+        assert_eq!(
+            next(
+                "FREQ=DAILY;COUNT=20",
+                "2024-01-01T09:00:00Z",
+                Tz::UTC,
+                "2024-01-10T12:00:00Z"
+            ),
+            Some((
+                utc("2024-01-11T09:00:00Z"),
+                "FREQ=DAILY;COUNT=10".to_string()
+            ))
+        );
+        assert_eq!(
+            next(
+                "FREQ=DAILY;COUNT=5",
+                "2024-01-01T09:00:00Z",
+                Tz::UTC,
+                "2024-01-10T12:00:00Z"
+            ),
+            None
+        );
+    }
+}
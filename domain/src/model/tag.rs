@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct TagEntity {
+    pub id: i64,
+    pub account: String,
+    pub name: String,
+}
+
+// A tag as attached to one todo.
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct TodoTagEntity {
+    pub todo_id: i64,
+    pub tag_id: i64,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TagMatch {
+    #[default]
+    All,
+    Any,
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use super::tag::TagMatch;
+
 #[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
 pub struct TodoEntity {
     pub id: i64,
@@ -9,4 +11,84 @@ pub struct TodoEntity {
     pub due_date: DateTime<Utc>,
     pub content: String,
     pub complete: bool,
+    pub public: bool,
+    // `Priority::level`
+    pub priority: i32,
+    pub parent_id: Option<i64>,
+    // Canonical `Recurrence`; handed on to the next occurrence when the todo is completed
+    pub recurrence: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub fn level(&self) -> i32 {
+        match self {
+            Priority::Low => 0,
+            Priority::Normal => 1,
+            Priority::High => 2,
+            Priority::Urgent => 3,
+        }
+    }
+
+    // Levels outside the known range are clamped rather than rejected.
+    pub fn from_level(level: i32) -> Self {
+        match level {
+            i32::MIN..=0 => Priority::Low,
+            1 => Priority::Normal,
+            2 => Priority::High,
+            _ => Priority::Urgent,
+        }
+    }
+}
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct ChecklistItemEntity {
+    pub id: i64,
+    pub todo_id: i64,
+    pub position: i32,
+    pub content: String,
+    pub checked: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TodoSort {
+    #[default]
+    Id,
+    DueDate,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TodoCursor {
+    pub sort: TodoSort,
+    pub descending: bool,
+    // Digest of the filters of the query the cursor was issued for
+    pub filter: String,
+    pub due_date: Option<DateTime<Utc>>,
+    pub id: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TodoQuery {
+    pub viewer: Option<String>,
+    pub account: Option<String>,
+    pub complete: Option<bool>,
+    pub due_from: Option<DateTime<Utc>>,
+    pub due_to: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    pub sort: TodoSort,
+    pub descending: bool,
+    pub cursor: Option<TodoCursor>,
+    pub offset: i64,
+    pub limit: i64,
 }
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct TotpEntity {
+    pub account: String,
+    pub secret: String,
+    pub confirmed: bool,
+    pub last_step: i64,
+}
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct RecoveryCodeEntity {
+    pub account: String,
+    pub code_hash: String,
+    pub used: bool,
+}
+
+#[derive(FromRow, Serialize, Deserialize, Clone, Debug)]
+pub struct SigninChallengeEntity {
+    pub token_hash: String,
+    pub account: String,
+    pub expires_at: DateTime<Utc>,
+    pub attempts: i64,
+}
@@ -1,7 +1,15 @@
 use async_trait::async_trait;
 
-use crate::interface::todo::TodoRepository;
+use crate::interface::api_key::ApiKeyRepository;
+use crate::interface::login_attempt::LoginAttemptRepository;
 use crate::interface::member::MemberRepository;
+use crate::interface::oidc::OidcRepository;
+use crate::interface::password_reset::PasswordResetRepository;
+use crate::interface::refresh_token::RefreshTokenRepository;
+use crate::interface::revoked_token::RevokedTokenRepository;
+use crate::interface::tag::TagRepository;
+use crate::interface::todo::{ChecklistRepository, TodoRepository};
+use crate::interface::two_factor::TwoFactorRepository;
 use common::types::BoxError;
 
 #[async_trait]
@@ -10,7 +18,16 @@ pub trait UnitOfWork: Send {
     async fn rollback(self: Box<Self>) -> Result<(), BoxError>;
 
     fn todo<'s>(&'s mut self) -> Box<dyn TodoRepository + 's>;
+    fn tag<'s>(&'s mut self) -> Box<dyn TagRepository + 's>;
+    fn checklist<'s>(&'s mut self) -> Box<dyn ChecklistRepository + 's>;
     fn member<'s>(&'s mut self) -> Box<dyn MemberRepository + 's>;
+    fn refresh_token<'s>(&'s mut self) -> Box<dyn RefreshTokenRepository + 's>;
+    fn revoked_token<'s>(&'s mut self) -> Box<dyn RevokedTokenRepository + 's>;
+    fn two_factor<'s>(&'s mut self) -> Box<dyn TwoFactorRepository + 's>;
+    fn login_attempt<'s>(&'s mut self) -> Box<dyn LoginAttemptRepository + 's>;
+    fn password_reset<'s>(&'s mut self) -> Box<dyn PasswordResetRepository + 's>;
+    fn api_key<'s>(&'s mut self) -> Box<dyn ApiKeyRepository + 's>;
+    fn oidc<'s>(&'s mut self) -> Box<dyn OidcRepository + 's>;
 }
 
 #[async_trait]
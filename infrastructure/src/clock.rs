@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use domain::interface::clock::Clock;
+
+#[derive(Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
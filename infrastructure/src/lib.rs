@@ -1,3 +1,5 @@
+pub mod clock;
+pub mod notifier;
 pub mod repository;
 
 mod uow;
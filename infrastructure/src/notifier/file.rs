@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use common::types::BoxError;
+use domain::{interface::notifier::Notifier, model::notification::Notification};
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+
+// Appends each notification as one JSON line, so a local mailbox can be tailed or scripted.
+pub struct FileNotifier {
+    path: PathBuf,
+}
+
+impl FileNotifier {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), BoxError> {
+        let mut line = serde_json::to_string(&serde_json::json!({
+            "sentAt": Utc::now(),
+            "recipient": notification.recipient,
+            "subject": notification.subject,
+            "body": notification.body,
+        }))?;
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
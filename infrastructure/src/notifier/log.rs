@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use common::types::BoxError;
+use domain::{interface::notifier::Notifier, model::notification::Notification};
+
+// Writes notifications to the application log; meant for local development only.
+#[derive(Default)]
+pub struct LogNotifier;
+
+impl LogNotifier {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, notification: &Notification) -> Result<(), BoxError> {
+        tracing::info!(
+            recipient = %notification.recipient,
+            subject = %notification.subject,
+            "->> Notification\n{}",
+            notification.body
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use common::types::BoxError;
+use domain::interface::notifier::Notifier;
+use std::sync::Arc;
+
+mod file;
+mod log;
+mod smtp;
+
+pub use file::FileNotifier;
+pub use log::LogNotifier;
+pub use smtp::SmtpNotifier;
+
+pub fn create(
+    kind: &str,
+    file: Option<&str>,
+    smtp_url: Option<&str>,
+    from: Option<&str>,
+) -> Result<Arc<dyn Notifier>, BoxError> {
+    match kind {
+        "log" => Ok(Arc::new(LogNotifier::new())),
+        "file" => match file {
+            Some(path) => Ok(Arc::new(FileNotifier::new(path))),
+            None => Err("notifier.file is required for the file notifier".into()),
+        },
+        "smtp" => match (smtp_url, from) {
+            (Some(url), Some(from)) => Ok(Arc::new(SmtpNotifier::new(url, from)?)),
+            _ => {
+                Err("notifier.smtp_url and notifier.from are required for the smtp notifier".into())
+            }
+        },
+        _ => Err(format!("unsupported notifier '{kind}'").into()),
+    }
+}
@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+use derive_new::new;
+use domain::{interface::api_key::ApiKeyRepository, model::api_key::ApiKeyEntity};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct ApiKeyRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> ApiKeyRepository for ApiKeyRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &ApiKeyEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO api_key (id,account,name,key_hash,scopes,created_at,expires_at,last_used_at) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)",
+                )
+                .bind(&entity.id)
+                .bind(&entity.account)
+                .bind(&entity.name)
+                .bind(&entity.key_hash)
+                .bind(&entity.scopes)
+                .bind(entity.created_at)
+                .bind(entity.expires_at)
+                .bind(entity.last_used_at)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn select_by_hash(&mut self, key_hash: &str) -> Result<Option<ApiKeyEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, ApiKeyEntity>("SELECT * FROM api_key WHERE key_hash=$1")
+                    .bind(key_hash)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+
+                Ok(rec)
+            }
+
+            async fn select_by_account(&mut self, account: &str) -> Result<Vec<ApiKeyEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, ApiKeyEntity>(
+                    "SELECT * FROM api_key WHERE account=$1 ORDER BY created_at, id",
+                )
+                .bind(account)
+                .fetch_all(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn touch(
+                &mut self,
+                id: &str,
+                now: DateTime<Utc>,
+                stale_before: DateTime<Utc>,
+            ) -> Result<(), BoxError> {
+                sqlx::query(
+                    "UPDATE api_key SET last_used_at=$1 WHERE id=$2 AND (last_used_at IS NULL OR last_used_at<$3)",
+                )
+                .bind(now)
+                .bind(id)
+                .bind(stale_before)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn delete(&mut self, account: &str, id: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM api_key WHERE account=$1 AND id=$2")
+                    .bind(account)
+                    .bind(id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM api_key WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn delete_expired(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM api_key WHERE expires_at<=$1")
+                    .bind(now)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::login_attempt::LoginAttemptRepository, model::login_attempt::LoginAttemptEntity,
+};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct LoginAttemptRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> LoginAttemptRepository for LoginAttemptRepositoryImpl<'a, $db> {
+            async fn select(
+                &mut self,
+                scope: &str,
+                subject: &str,
+            ) -> Result<Option<LoginAttemptEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, LoginAttemptEntity>(
+                    "SELECT * FROM login_attempt WHERE scope=$1 AND subject=$2",
+                )
+                .bind(scope)
+                .bind(subject)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn record_failure(
+                &mut self,
+                scope: &str,
+                subject: &str,
+                now: DateTime<Utc>,
+                reset_before: DateTime<Utc>,
+            ) -> Result<LoginAttemptEntity, BoxError> {
+                let rec = sqlx::query_as::<_, LoginAttemptEntity>(
+                    "INSERT INTO login_attempt (scope,subject,failures,blocked_until,last_failed_at) VALUES ($1,$2,1,NULL,$3) ON CONFLICT(scope,subject) DO UPDATE SET failures=CASE WHEN login_attempt.last_failed_at<$4 THEN 1 ELSE login_attempt.failures+1 END, last_failed_at=$3 RETURNING *",
+                )
+                .bind(scope)
+                .bind(subject)
+                .bind(now)
+                .bind(reset_before)
+                .fetch_one(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn block(
+                &mut self,
+                scope: &str,
+                subject: &str,
+                until: DateTime<Utc>,
+            ) -> Result<(), BoxError> {
+                sqlx::query("UPDATE login_attempt SET blocked_until=$1 WHERE scope=$2 AND subject=$3")
+                    .bind(until)
+                    .bind(scope)
+                    .bind(subject)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn delete(&mut self, scope: &str, subject: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM login_attempt WHERE scope=$1 AND subject=$2")
+                    .bind(scope)
+                    .bind(subject)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn delete_stale(&mut self, before: DateTime<Utc>) -> Result<u64, BoxError> {
+                let result = sqlx::query(
+                    "DELETE FROM login_attempt WHERE last_failed_at<$1 AND (blocked_until IS NULL OR blocked_until<$1)",
+                )
+                .bind(before)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
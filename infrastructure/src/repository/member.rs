@@ -1,31 +1,182 @@
 use async_trait::async_trait;
-use common::types::{BoxError, DbExecutor};
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
 use derive_new::new;
-use domain::{interface::member::MemberRepository, model::member::MemberEntity};
+use domain::{
+    interface::member::MemberRepository,
+    model::member::{MemberEntity, Role},
+};
+use sqlx::Database;
 
-#[derive(new, Debug)]
-pub struct MemberRepositoryImpl<'a> {
-    executor: &'a mut DbExecutor,
+#[derive(new)]
+pub struct MemberRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
 }
 
-#[async_trait]
-impl<'a> MemberRepository for MemberRepositoryImpl<'a> {
-    async fn insert(&mut self, entity: &MemberEntity) -> Result<MemberEntity, BoxError> {
-        let rec = sqlx::query_as::<_, MemberEntity>("INSERT INTO member (account,password) VALUES ($1,$2) RETURNING *")
-            .bind(&entity.account)
-            .bind(&entity.password)
-            .fetch_one(&mut *self.executor)
-            .await?;
-
-        Ok(rec)
-    }
-
-    async fn select(&mut self, account: &str) -> Result<Option<MemberEntity>, BoxError> {
-        let rec = sqlx::query_as::<_, MemberEntity>("SELECT * FROM member WHERE account=$1")
-            .bind(&account)
-            .fetch_optional(&mut *self.executor)
-            .await?;
-
-        Ok(rec)
-    }
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> MemberRepository for MemberRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &MemberEntity) -> Result<MemberEntity, BoxError> {
+                let rec = sqlx::query_as::<_, MemberEntity>(
+                    "INSERT INTO member (account,password,email,verified) VALUES ($1,$2,$3,$4) RETURNING *",
+                )
+                .bind(&entity.account)
+                .bind(&entity.password)
+                .bind(&entity.email)
+                .bind(entity.verified)
+                .fetch_one(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn select(&mut self, account: &str) -> Result<Option<MemberEntity>, BoxError> {
+                let rec =
+                    sqlx::query_as::<_, MemberEntity>("SELECT * FROM member WHERE account=$1")
+                        .bind(account)
+                        .fetch_optional(&mut *self.executor)
+                        .await?;
+
+                Ok(rec)
+            }
+
+            async fn select_page(
+                &mut self,
+                offset: i64,
+                limit: i64,
+            ) -> Result<Vec<MemberEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, MemberEntity>(
+                    "SELECT * FROM member ORDER BY account LIMIT $1 OFFSET $2",
+                )
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn update_disabled(
+                &mut self,
+                account: &str,
+                disabled: bool,
+            ) -> Result<bool, BoxError> {
+                let result = sqlx::query("UPDATE member SET disabled=$1 WHERE account=$2")
+                    .bind(disabled)
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn update_password(
+                &mut self,
+                account: &str,
+                password: &str,
+            ) -> Result<bool, BoxError> {
+                let result = sqlx::query("UPDATE member SET password=$1 WHERE account=$2")
+                    .bind(password)
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn update_verified(
+                &mut self,
+                account: &str,
+                email: &str,
+            ) -> Result<bool, BoxError> {
+                let result =
+                    sqlx::query("UPDATE member SET verified=TRUE WHERE account=$1 AND email=$2")
+                        .bind(account)
+                        .bind(email)
+                        .execute(&mut *self.executor)
+                        .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn update_verification_sent(
+                &mut self,
+                account: &str,
+                at: DateTime<Utc>,
+                since: DateTime<Utc>,
+            ) -> Result<bool, BoxError> {
+                let result = sqlx::query(
+                    "UPDATE member SET verification_sent_at=$1 WHERE account=$2 AND verified=FALSE AND (verification_sent_at IS NULL OR verification_sent_at<=$3)",
+                )
+                .bind(at)
+                .bind(account)
+                .bind(since)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn update_timezone(
+                &mut self,
+                account: &str,
+                timezone: Option<&str>,
+            ) -> Result<bool, BoxError> {
+                let result = sqlx::query("UPDATE member SET timezone=$1 WHERE account=$2")
+                    .bind(timezone)
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn delete(&mut self, account: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM member WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn select_roles(&mut self, account: &str) -> Result<Vec<Role>, BoxError> {
+                let rec = sqlx::query_scalar::<_, String>(
+                    "SELECT role FROM member_role WHERE account=$1 ORDER BY role",
+                )
+                .bind(account)
+                .fetch_all(&mut *self.executor)
+                .await?;
+
+                Ok(rec.iter().filter_map(|r| r.parse().ok()).collect())
+            }
+
+            async fn insert_role(&mut self, account: &str, role: Role) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO member_role (account,role) VALUES ($1,$2) ON CONFLICT DO NOTHING",
+                )
+                .bind(account)
+                .bind(role.as_str())
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn delete_roles(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM member_role WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
 }
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
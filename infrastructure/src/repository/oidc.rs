@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::oidc::OidcRepository,
+    model::oidc::{AuthorizationCodeEntity, OidcClientEntity},
+};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct OidcRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> OidcRepository for OidcRepositoryImpl<'a, $db> {
+            async fn insert_client(&mut self, entity: &OidcClientEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO oidc_client (client_id,name,secret_hash,redirect_uris,created_at) VALUES ($1,$2,$3,$4,$5)",
+                )
+                .bind(&entity.client_id)
+                .bind(&entity.name)
+                .bind(&entity.secret_hash)
+                .bind(&entity.redirect_uris)
+                .bind(entity.created_at)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn select_client(
+                &mut self,
+                client_id: &str,
+            ) -> Result<Option<OidcClientEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, OidcClientEntity>(
+                    "SELECT * FROM oidc_client WHERE client_id=$1",
+                )
+                .bind(client_id)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn select_clients(&mut self) -> Result<Vec<OidcClientEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, OidcClientEntity>(
+                    "SELECT * FROM oidc_client ORDER BY created_at, client_id",
+                )
+                .fetch_all(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn delete_client(&mut self, client_id: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM oidc_client WHERE client_id=$1")
+                    .bind(client_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn insert_code(&mut self, entity: &AuthorizationCodeEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO oidc_authorization_code (code_hash,client_id,account,redirect_uri,scope,nonce,code_challenge,expires_at) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)",
+                )
+                .bind(&entity.code_hash)
+                .bind(&entity.client_id)
+                .bind(&entity.account)
+                .bind(&entity.redirect_uri)
+                .bind(&entity.scope)
+                .bind(&entity.nonce)
+                .bind(&entity.code_challenge)
+                .bind(entity.expires_at)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn take_code(
+                &mut self,
+                code_hash: &str,
+            ) -> Result<Option<AuthorizationCodeEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, AuthorizationCodeEntity>(
+                    "DELETE FROM oidc_authorization_code WHERE code_hash=$1 RETURNING *",
+                )
+                .bind(code_hash)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn delete_codes_by_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM oidc_authorization_code WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn delete_codes_by_client(&mut self, client_id: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM oidc_authorization_code WHERE client_id=$1")
+                    .bind(client_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn delete_expired_codes(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM oidc_authorization_code WHERE expires_at<$1")
+                    .bind(now)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::password_reset::PasswordResetRepository, model::password_reset::PasswordResetEntity,
+};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct PasswordResetRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> PasswordResetRepository for PasswordResetRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &PasswordResetEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO password_reset (token_hash,account,expires_at) VALUES ($1,$2,$3)",
+                )
+                .bind(&entity.token_hash)
+                .bind(&entity.account)
+                .bind(entity.expires_at)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn select(
+                &mut self,
+                token_hash: &str,
+            ) -> Result<Option<PasswordResetEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, PasswordResetEntity>(
+                    "SELECT * FROM password_reset WHERE token_hash=$1",
+                )
+                .bind(token_hash)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM password_reset WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn delete_expired(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM password_reset WHERE expires_at<=$1")
+                    .bind(now)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::refresh_token::RefreshTokenRepository, model::refresh_token::RefreshTokenEntity,
+};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct RefreshTokenRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> RefreshTokenRepository for RefreshTokenRepositoryImpl<'a, $db> {
+            async fn insert(
+                &mut self,
+                entity: &RefreshTokenEntity,
+            ) -> Result<RefreshTokenEntity, BoxError> {
+                let rec = sqlx::query_as::<_, RefreshTokenEntity>(
+                    "INSERT INTO refresh_token (token_hash,family,account,expires_at,used,revoked) VALUES ($1,$2,$3,$4,$5,$6) RETURNING *",
+                )
+                .bind(&entity.token_hash)
+                .bind(&entity.family)
+                .bind(&entity.account)
+                .bind(entity.expires_at)
+                .bind(entity.used)
+                .bind(entity.revoked)
+                .fetch_one(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn select(&mut self, token_hash: &str) -> Result<Option<RefreshTokenEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, RefreshTokenEntity>(
+                    "SELECT * FROM refresh_token WHERE token_hash=$1",
+                )
+                .bind(token_hash)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn mark_used(&mut self, token_hash: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query(
+                    "UPDATE refresh_token SET used=TRUE WHERE token_hash=$1 AND used=FALSE AND revoked=FALSE",
+                )
+                .bind(token_hash)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(result.rows_affected() == 1)
+            }
+
+            async fn revoke_family(&mut self, family: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("UPDATE refresh_token SET revoked=TRUE WHERE family=$1")
+                    .bind(family)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn revoke_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("UPDATE refresh_token SET revoked=TRUE WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::revoked_token::RevokedTokenRepository, model::revoked_token::RevokedTokenEntity,
+};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct RevokedTokenRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> RevokedTokenRepository for RevokedTokenRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &RevokedTokenEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO revoked_token (jti,account,expires_at) VALUES ($1,$2,$3) ON CONFLICT(jti) DO NOTHING",
+                )
+                .bind(&entity.jti)
+                .bind(&entity.account)
+                .bind(entity.expires_at)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn exists(&mut self, jti: &str) -> Result<bool, BoxError> {
+                let rec = sqlx::query("SELECT jti FROM revoked_token WHERE jti=$1")
+                    .bind(jti)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+
+                Ok(rec.is_some())
+            }
+
+            async fn delete_expired(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM revoked_token WHERE expires_at<$1")
+                    .bind(now)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn revoke_account(&mut self, account: &str, at: DateTime<Utc>) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO account_revocation (account,revoked_at) VALUES ($1,$2) ON CONFLICT(account) DO UPDATE SET revoked_at=excluded.revoked_at",
+                )
+                .bind(account)
+                .bind(at)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn select_account_revoked_at(
+                &mut self,
+                account: &str,
+            ) -> Result<Option<DateTime<Utc>>, BoxError> {
+                let rec = sqlx::query_scalar::<_, DateTime<Utc>>(
+                    "SELECT revoked_at FROM account_revocation WHERE account=$1",
+                )
+                .bind(account)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::tag::TagRepository,
+    model::tag::{TagEntity, TodoTagEntity},
+};
+use sqlx::{Database, QueryBuilder};
+
+#[derive(new)]
+pub struct TagRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> TagRepository for TagRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &TagEntity) -> Result<TagEntity, BoxError> {
+                let rec = sqlx::query_as::<_, TagEntity>(
+                    "INSERT INTO tag (account,name) VALUES ($1,$2) RETURNING *",
+                )
+                .bind(&entity.account)
+                .bind(&entity.name)
+                .fetch_one(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn select(&mut self, account: &str, id: i64) -> Result<Option<TagEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TagEntity>("SELECT * FROM tag WHERE account=$1 AND id=$2")
+                    .bind(account)
+                    .bind(id)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+
+                Ok(rec)
+            }
+
+            async fn select_by_account(&mut self, account: &str) -> Result<Vec<TagEntity>, BoxError> {
+                let recs = sqlx::query_as::<_, TagEntity>("SELECT * FROM tag WHERE account=$1 ORDER BY name")
+                    .bind(account)
+                    .fetch_all(&mut *self.executor)
+                    .await?;
+
+                Ok(recs)
+            }
+
+            async fn select_by_names(
+                &mut self,
+                account: &str,
+                names: &[String],
+            ) -> Result<Vec<TagEntity>, BoxError> {
+                if names.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let mut qb = QueryBuilder::<$db>::new("SELECT * FROM tag WHERE account=");
+                qb.push_bind(account.to_string()).push(" AND name IN (");
+                let mut separated = qb.separated(",");
+                for name in names {
+                    separated.push_bind(name.clone());
+                }
+                qb.push(")");
+
+                let recs = qb
+                    .build_query_as::<TagEntity>()
+                    .fetch_all(&mut *self.executor)
+                    .await?;
+
+                Ok(recs)
+            }
+
+            async fn update(&mut self, entity: &TagEntity) -> Result<Option<TagEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TagEntity>(
+                    "UPDATE tag SET name=$1 WHERE account=$2 AND id=$3 RETURNING *",
+                )
+                .bind(&entity.name)
+                .bind(&entity.account)
+                .bind(entity.id)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn delete(&mut self, account: &str, id: i64) -> Result<Option<TagEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TagEntity>("DELETE FROM tag WHERE account=$1 AND id=$2 RETURNING *")
+                    .bind(account)
+                    .bind(id)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+                if let Some(tag) = &rec {
+                    sqlx::query("DELETE FROM todo_tag WHERE tag_id=$1")
+                        .bind(tag.id)
+                        .execute(&mut *self.executor)
+                        .await?;
+                }
+
+                Ok(rec)
+            }
+
+            async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                sqlx::query("DELETE FROM todo_tag WHERE tag_id IN (SELECT id FROM tag WHERE account=$1)")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+                let result = sqlx::query("DELETE FROM tag WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn select_by_todos(&mut self, todo_ids: &[i64]) -> Result<Vec<TodoTagEntity>, BoxError> {
+                if todo_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let mut qb = QueryBuilder::<$db>::new(
+                    "SELECT todo_tag.todo_id,todo_tag.tag_id,tag.name FROM todo_tag JOIN tag ON tag.id=todo_tag.tag_id WHERE todo_tag.todo_id IN (",
+                );
+                let mut separated = qb.separated(",");
+                for id in todo_ids {
+                    separated.push_bind(*id);
+                }
+                qb.push(") ORDER BY todo_tag.todo_id, tag.name");
+
+                let recs = qb
+                    .build_query_as::<TodoTagEntity>()
+                    .fetch_all(&mut *self.executor)
+                    .await?;
+
+                Ok(recs)
+            }
+
+            async fn assign(&mut self, todo_id: i64, tag_id: i64) -> Result<(), BoxError> {
+                sqlx::query("INSERT INTO todo_tag (todo_id,tag_id) VALUES ($1,$2) ON CONFLICT DO NOTHING")
+                    .bind(todo_id)
+                    .bind(tag_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn unassign(&mut self, todo_id: i64, tag_id: i64) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM todo_tag WHERE todo_id=$1 AND tag_id=$2")
+                    .bind(todo_id)
+                    .bind(tag_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn unassign_by_todo(&mut self, todo_id: i64) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM todo_tag WHERE todo_id=$1")
+                    .bind(todo_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -1,35 +1,253 @@
 use async_trait::async_trait;
-use common::types::{BoxError, DbExecutor};
+use common::types::BoxError;
 use derive_new::new;
-use domain::{interface::todo::TodoRepository, model::todo::TodoEntity};
+use domain::{
+    interface::todo::{ChecklistRepository, TodoRepository},
+    model::{
+        tag::TagMatch,
+        todo::{ChecklistItemEntity, TodoEntity, TodoQuery, TodoSort},
+    },
+};
+use sqlx::{Database, QueryBuilder};
 
-#[derive(new, Debug)]
-pub struct TodoRepositoryImpl<'a> {
-    executor: &'a mut DbExecutor,
+#[derive(new)]
+pub struct TodoRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
 }
 
-#[async_trait]
-impl<'a> TodoRepository for TodoRepositoryImpl<'a> {
-    async fn insert(&mut self, entity: &TodoEntity) -> Result<TodoEntity, BoxError> {
-        let rec = sqlx::query_as::<_, TodoEntity>(
-            "INSERT INTO todo (account,due_date,content,complete) VALUES ($1,$2,$3,$4) RETURNING *",
-        )
-        .bind(&entity.account)
-        .bind(&entity.due_date)
-        .bind(&entity.content)
-        .bind(&entity.complete)
-        .fetch_one(&mut *self.executor)
-        .await?;
-
-        Ok(rec)
-    }
-
-    async fn selectl(&mut self, id: i64) -> Result<Option<TodoEntity>, BoxError> {
-        let rec = sqlx::query_as::<_, TodoEntity>("SELECT * FROM todo WHERE id=$1")
-            .bind(&id)
-            .fetch_optional(&mut *self.executor)
-            .await?;
-
-        Ok(rec)
-    }
+#[derive(new)]
+pub struct ChecklistRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
 }
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> TodoRepository for TodoRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &TodoEntity) -> Result<TodoEntity, BoxError> {
+                let rec = sqlx::query_as::<_, TodoEntity>(
+                    "INSERT INTO todo (account,due_date,content,complete,public,priority,parent_id,recurrence) VALUES ($1,$2,$3,$4,$5,$6,$7,$8) RETURNING *",
+                )
+                .bind(&entity.account)
+                .bind(entity.due_date)
+                .bind(&entity.content)
+                .bind(entity.complete)
+                .bind(entity.public)
+                .bind(entity.priority)
+                .bind(entity.parent_id)
+                .bind(&entity.recurrence)
+                .fetch_one(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn selectl(&mut self, id: i64) -> Result<Option<TodoEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TodoEntity>("SELECT * FROM todo WHERE id=$1")
+                    .bind(id)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+
+                Ok(rec)
+            }
+
+            async fn select_page(&mut self, query: &TodoQuery) -> Result<Vec<TodoEntity>, BoxError> {
+                let mut qb = QueryBuilder::<$db>::new("SELECT * FROM todo WHERE (public=TRUE OR account=");
+                qb.push_bind(query.viewer.clone()).push(")");
+
+                if let Some(account) = &query.account {
+                    qb.push(" AND account=").push_bind(account.clone());
+                }
+                if let Some(complete) = query.complete {
+                    qb.push(" AND complete=").push_bind(complete);
+                }
+                if let Some(from) = query.due_from {
+                    qb.push(" AND due_date>=").push_bind(from);
+                }
+                if let Some(to) = query.due_to {
+                    qb.push(" AND due_date<").push_bind(to);
+                }
+                if !query.tags.is_empty() {
+                    qb.push(" AND id IN (SELECT todo_tag.todo_id FROM todo_tag JOIN tag ON tag.id=todo_tag.tag_id WHERE tag.name IN (");
+                    let mut names = qb.separated(",");
+                    for name in &query.tags {
+                        names.push_bind(name.clone());
+                    }
+                    qb.push(")");
+                    // Tag names are unique per account, so a todo carrying all of them has one row per name.
+                    if query.tag_match == TagMatch::All {
+                        qb.push(" GROUP BY todo_tag.todo_id HAVING COUNT(*)=")
+                            .push_bind(query.tags.len() as i64);
+                    }
+                    qb.push(")");
+                }
+
+                let (cmp, order) = if query.descending {
+                    ("<", "DESC")
+                } else {
+                    (">", "ASC")
+                };
+                if let Some(cursor) = &query.cursor {
+                    match (query.sort, cursor.due_date) {
+                        (TodoSort::DueDate, Some(due_date)) => {
+                            qb.push(format!(" AND (due_date{cmp}"))
+                                .push_bind(due_date)
+                                .push(" OR (due_date=")
+                                .push_bind(due_date)
+                                .push(format!(" AND id{cmp}"))
+                                .push_bind(cursor.id)
+                                .push("))");
+                        }
+                        (TodoSort::DueDate, None) => {
+                            return Err("due date cursor without a due date".into());
+                        }
+                        (TodoSort::Id, _) => {
+                            qb.push(format!(" AND id{cmp}")).push_bind(cursor.id);
+                        }
+                    }
+                }
+
+                match query.sort {
+                    TodoSort::Id => qb.push(format!(" ORDER BY id {order}")),
+                    TodoSort::DueDate => qb.push(format!(" ORDER BY due_date {order}, id {order}")),
+                };
+                qb.push(" LIMIT ").push_bind(query.limit);
+                if query.cursor.is_none() {
+                    qb.push(" OFFSET ").push_bind(query.offset);
+                }
+
+                let recs = qb
+                    .build_query_as::<TodoEntity>()
+                    .fetch_all(&mut *self.executor)
+                    .await?;
+
+                Ok(recs)
+            }
+
+            async fn select_by_parents(&mut self, parent_ids: &[i64]) -> Result<Vec<TodoEntity>, BoxError> {
+                if parent_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let mut qb = QueryBuilder::<$db>::new("SELECT * FROM todo WHERE parent_id IN (");
+                let mut separated = qb.separated(",");
+                for id in parent_ids {
+                    separated.push_bind(*id);
+                }
+                qb.push(") ORDER BY id");
+
+                let recs = qb
+                    .build_query_as::<TodoEntity>()
+                    .fetch_all(&mut *self.executor)
+                    .await?;
+
+                Ok(recs)
+            }
+
+            async fn update(&mut self, entity: &TodoEntity) -> Result<Option<TodoEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TodoEntity>(
+                    "UPDATE todo SET account=$1,due_date=$2,content=$3,complete=$4,public=$5,priority=$6,parent_id=$7,recurrence=$8 WHERE id=$9 RETURNING *",
+                )
+                .bind(&entity.account)
+                .bind(entity.due_date)
+                .bind(&entity.content)
+                .bind(entity.complete)
+                .bind(entity.public)
+                .bind(entity.priority)
+                .bind(entity.parent_id)
+                .bind(&entity.recurrence)
+                .bind(entity.id)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn delete(&mut self, id: i64) -> Result<Option<TodoEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TodoEntity>("DELETE FROM todo WHERE id=$1 RETURNING *")
+                    .bind(id)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+
+                Ok(rec)
+            }
+
+            async fn detach_children(&mut self, parent_id: i64) -> Result<u64, BoxError> {
+                let result = sqlx::query("UPDATE todo SET parent_id=NULL WHERE parent_id=$1")
+                    .bind(parent_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM todo WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+
+        #[async_trait]
+        impl<'a> ChecklistRepository for ChecklistRepositoryImpl<'a, $db> {
+            async fn insert(&mut self, entity: &ChecklistItemEntity) -> Result<(), BoxError> {
+                sqlx::query("INSERT INTO checklist_item (todo_id,position,content,checked) VALUES ($1,$2,$3,$4)")
+                    .bind(entity.todo_id)
+                    .bind(entity.position)
+                    .bind(&entity.content)
+                    .bind(entity.checked)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn select_by_todos(
+                &mut self,
+                todo_ids: &[i64],
+            ) -> Result<Vec<ChecklistItemEntity>, BoxError> {
+                if todo_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                let mut qb = QueryBuilder::<$db>::new("SELECT * FROM checklist_item WHERE todo_id IN (");
+                let mut separated = qb.separated(",");
+                for id in todo_ids {
+                    separated.push_bind(*id);
+                }
+                qb.push(") ORDER BY todo_id, position");
+
+                let recs = qb
+                    .build_query_as::<ChecklistItemEntity>()
+                    .fetch_all(&mut *self.executor)
+                    .await?;
+
+                Ok(recs)
+            }
+
+            async fn delete_by_todo(&mut self, todo_id: i64) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM checklist_item WHERE todo_id=$1")
+                    .bind(todo_id)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn delete_by_account(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM checklist_item WHERE todo_id IN (SELECT id FROM todo WHERE account=$1)")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::types::BoxError;
+use derive_new::new;
+use domain::{
+    interface::two_factor::TwoFactorRepository,
+    model::two_factor::{RecoveryCodeEntity, SigninChallengeEntity, TotpEntity},
+};
+use sqlx::Database;
+
+#[derive(new)]
+pub struct TwoFactorRepositoryImpl<'a, DB: Database> {
+    executor: &'a mut DB::Connection,
+}
+
+macro_rules! impl_repository {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> TwoFactorRepository for TwoFactorRepositoryImpl<'a, $db> {
+            async fn select_totp(&mut self, account: &str) -> Result<Option<TotpEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, TotpEntity>("SELECT * FROM member_totp WHERE account=$1")
+                    .bind(account)
+                    .fetch_optional(&mut *self.executor)
+                    .await?;
+
+                Ok(rec)
+            }
+
+            async fn upsert_totp(&mut self, entity: &TotpEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO member_totp (account,secret,confirmed,last_step) VALUES ($1,$2,$3,$4) ON CONFLICT(account) DO UPDATE SET secret=excluded.secret, confirmed=excluded.confirmed, last_step=excluded.last_step",
+                )
+                .bind(&entity.account)
+                .bind(&entity.secret)
+                .bind(entity.confirmed)
+                .bind(entity.last_step)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn confirm_totp(&mut self, account: &str, step: i64) -> Result<bool, BoxError> {
+                let result = sqlx::query(
+                    "UPDATE member_totp SET confirmed=TRUE, last_step=$1 WHERE account=$2 AND confirmed=FALSE",
+                )
+                .bind(step)
+                .bind(account)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(result.rows_affected() == 1)
+            }
+
+            async fn advance_totp_step(&mut self, account: &str, step: i64) -> Result<bool, BoxError> {
+                let result = sqlx::query(
+                    "UPDATE member_totp SET last_step=$1 WHERE account=$2 AND last_step<$1",
+                )
+                .bind(step)
+                .bind(account)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(result.rows_affected() == 1)
+            }
+
+            async fn delete_totp(&mut self, account: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM member_totp WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() > 0)
+            }
+
+            async fn insert_recovery_code(&mut self, entity: &RecoveryCodeEntity) -> Result<(), BoxError> {
+                sqlx::query("INSERT INTO recovery_code (account,code_hash,used) VALUES ($1,$2,$3)")
+                    .bind(&entity.account)
+                    .bind(&entity.code_hash)
+                    .bind(entity.used)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn select_unused_recovery_codes(
+                &mut self,
+                account: &str,
+            ) -> Result<Vec<RecoveryCodeEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, RecoveryCodeEntity>(
+                    "SELECT * FROM recovery_code WHERE account=$1 AND used=FALSE",
+                )
+                .bind(account)
+                .fetch_all(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn use_recovery_code(&mut self, account: &str, code_hash: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query(
+                    "UPDATE recovery_code SET used=TRUE WHERE account=$1 AND code_hash=$2 AND used=FALSE",
+                )
+                .bind(account)
+                .bind(code_hash)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(result.rows_affected() == 1)
+            }
+
+            async fn delete_recovery_codes(&mut self, account: &str) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM recovery_code WHERE account=$1")
+                    .bind(account)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+
+            async fn insert_challenge(&mut self, entity: &SigninChallengeEntity) -> Result<(), BoxError> {
+                sqlx::query(
+                    "INSERT INTO signin_challenge (token_hash,account,expires_at,attempts) VALUES ($1,$2,$3,$4)",
+                )
+                .bind(&entity.token_hash)
+                .bind(&entity.account)
+                .bind(entity.expires_at)
+                .bind(entity.attempts)
+                .execute(&mut *self.executor)
+                .await?;
+
+                Ok(())
+            }
+
+            async fn select_challenge(
+                &mut self,
+                token_hash: &str,
+            ) -> Result<Option<SigninChallengeEntity>, BoxError> {
+                let rec = sqlx::query_as::<_, SigninChallengeEntity>(
+                    "SELECT * FROM signin_challenge WHERE token_hash=$1",
+                )
+                .bind(token_hash)
+                .fetch_optional(&mut *self.executor)
+                .await?;
+
+                Ok(rec)
+            }
+
+            async fn increment_challenge_attempts(&mut self, token_hash: &str) -> Result<(), BoxError> {
+                sqlx::query("UPDATE signin_challenge SET attempts=attempts+1 WHERE token_hash=$1")
+                    .bind(token_hash)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(())
+            }
+
+            async fn delete_challenge(&mut self, token_hash: &str) -> Result<bool, BoxError> {
+                let result = sqlx::query("DELETE FROM signin_challenge WHERE token_hash=$1")
+                    .bind(token_hash)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected() == 1)
+            }
+
+            async fn delete_expired_challenges(&mut self, now: DateTime<Utc>) -> Result<u64, BoxError> {
+                let result = sqlx::query("DELETE FROM signin_challenge WHERE expires_at<=$1")
+                    .bind(now)
+                    .execute(&mut *self.executor)
+                    .await?;
+
+                Ok(result.rows_affected())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlite")]
+impl_repository!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_repository!(sqlx::Postgres);
@@ -1,36 +1,87 @@
 use async_trait::async_trait;
 
-use common::types::{BoxError, Db, DbPool};
+use common::types::{BoxError, DbPool};
 use domain::{
-    UnitOfWork, UnitOfWorkProvider, interface::todo::TodoRepository,
-    interface::member::MemberRepository,
+    UnitOfWork, UnitOfWorkProvider, interface::api_key::ApiKeyRepository,
+    interface::login_attempt::LoginAttemptRepository,
+    interface::member::MemberRepository, interface::oidc::OidcRepository,
+    interface::password_reset::PasswordResetRepository,
+    interface::refresh_token::RefreshTokenRepository,
+    interface::revoked_token::RevokedTokenRepository, interface::tag::TagRepository,
+    interface::todo::{ChecklistRepository, TodoRepository},
+    interface::two_factor::TwoFactorRepository,
 };
+use sqlx::Database;
 
-use crate::repository::{todo::TodoRepositoryImpl, member::MemberRepositoryImpl};
+use crate::repository::{
+    api_key::ApiKeyRepositoryImpl,
+    login_attempt::LoginAttemptRepositoryImpl, member::MemberRepositoryImpl,
+    oidc::OidcRepositoryImpl,
+    password_reset::PasswordResetRepositoryImpl, refresh_token::RefreshTokenRepositoryImpl,
+    revoked_token::RevokedTokenRepositoryImpl, tag::TagRepositoryImpl,
+    todo::{ChecklistRepositoryImpl, TodoRepositoryImpl},
+    two_factor::TwoFactorRepositoryImpl,
+};
 
-pub struct UnitOfWorkImpl<'a> {
-    tx: sqlx::Transaction<'a, Db>,
+pub struct UnitOfWorkImpl<'a, DB: Database> {
+    tx: sqlx::Transaction<'a, DB>,
 }
 
-#[async_trait]
-impl<'a> UnitOfWork for UnitOfWorkImpl<'a> {
-    async fn commit(self: Box<Self>) -> Result<(), BoxError> {
-        self.tx.commit().await?;
-        Ok(())
-    }
-    async fn rollback(self: Box<Self>) -> Result<(), BoxError> {
-        self.tx.rollback().await?;
-        Ok(())
-    }
+macro_rules! impl_unit_of_work {
+    ($db:ty) => {
+        #[async_trait]
+        impl<'a> UnitOfWork for UnitOfWorkImpl<'a, $db> {
+            async fn commit(self: Box<Self>) -> Result<(), BoxError> {
+                self.tx.commit().await?;
+                Ok(())
+            }
+            async fn rollback(self: Box<Self>) -> Result<(), BoxError> {
+                self.tx.rollback().await?;
+                Ok(())
+            }
 
-    fn todo<'s>(&'s mut self) -> Box<dyn TodoRepository + 's> {
-        Box::new(TodoRepositoryImpl::new(&mut self.tx))
-    }
-    fn member<'s>(&'s mut self) -> Box<dyn MemberRepository + 's> {
-        Box::new(MemberRepositoryImpl::new(&mut self.tx))
-    }
+            fn todo<'s>(&'s mut self) -> Box<dyn TodoRepository + 's> {
+                Box::new(TodoRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn tag<'s>(&'s mut self) -> Box<dyn TagRepository + 's> {
+                Box::new(TagRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn checklist<'s>(&'s mut self) -> Box<dyn ChecklistRepository + 's> {
+                Box::new(ChecklistRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn member<'s>(&'s mut self) -> Box<dyn MemberRepository + 's> {
+                Box::new(MemberRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn refresh_token<'s>(&'s mut self) -> Box<dyn RefreshTokenRepository + 's> {
+                Box::new(RefreshTokenRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn revoked_token<'s>(&'s mut self) -> Box<dyn RevokedTokenRepository + 's> {
+                Box::new(RevokedTokenRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn two_factor<'s>(&'s mut self) -> Box<dyn TwoFactorRepository + 's> {
+                Box::new(TwoFactorRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn login_attempt<'s>(&'s mut self) -> Box<dyn LoginAttemptRepository + 's> {
+                Box::new(LoginAttemptRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn password_reset<'s>(&'s mut self) -> Box<dyn PasswordResetRepository + 's> {
+                Box::new(PasswordResetRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn api_key<'s>(&'s mut self) -> Box<dyn ApiKeyRepository + 's> {
+                Box::new(ApiKeyRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+            fn oidc<'s>(&'s mut self) -> Box<dyn OidcRepository + 's> {
+                Box::new(OidcRepositoryImpl::<$db>::new(&mut self.tx))
+            }
+        }
+    };
 }
 
+#[cfg(feature = "sqlite")]
+impl_unit_of_work!(sqlx::Sqlite);
+#[cfg(feature = "postgres")]
+impl_unit_of_work!(sqlx::Postgres);
+
 pub struct UnitOfWorkProviderImpl {
     pool: DbPool,
 }
@@ -44,7 +95,15 @@ impl UnitOfWorkProviderImpl {
 #[async_trait]
 impl UnitOfWorkProvider for UnitOfWorkProviderImpl {
     async fn begin(&self) -> Result<Box<dyn UnitOfWork + '_>, BoxError> {
-        let tx = self.pool.begin().await?;
-        Ok(Box::new(UnitOfWorkImpl { tx }))
+        match &self.pool {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => Ok(Box::new(UnitOfWorkImpl {
+                tx: pool.begin().await?,
+            })),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => Ok(Box::new(UnitOfWorkImpl {
+                tx: pool.begin().await?,
+            })),
+        }
     }
 }
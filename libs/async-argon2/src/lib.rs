@@ -1,25 +1,49 @@
 use argon2::{
-    password_hash::{rand_core::OsRng, SaltString},
-    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version,
+    password_hash::{SaltString, rand_core::OsRng},
 };
-use tokio::task;
+use std::sync::{LazyLock, OnceLock};
+use tokio::{sync::Semaphore, task};
 
 pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
 
+// Hashing is CPU-bound, so running more jobs than cores only queues them on the blocking
+// pool; bounding them keeps a flood of signins from starving other blocking work.
+static PERMITS: LazyLock<Semaphore> =
+    LazyLock::new(|| Semaphore::new(std::thread::available_parallelism().map_or(4, |n| n.get())));
+
+static PARAMS: OnceLock<Params> = OnceLock::new();
+static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+
+// Sets the cost parameters used for new hashes; defaults to the argon2 crate's defaults.
+// Can only be set once per process.
+pub fn configure(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<(), BoxError> {
+    let params = Params::new(memory_kib, iterations, parallelism, None)?;
+    let current = PARAMS.get_or_init(|| params.clone());
+    if *current != params {
+        return Err("argon2 parameters are already configured".into());
+    }
+    dummy_hash()?;
+    Ok(())
+}
+
 pub async fn hash(password: String) -> Result<String, BoxError> {
+    let _permit = PERMITS.acquire().await?;
     task::spawn_blocking(move || {
         let salt = SaltString::generate(&mut OsRng);
-        Ok(Argon2::default()
+        Ok(hasher()
             .hash_password(password.as_bytes(), &salt)?
             .to_string())
     })
     .await?
 }
 
+// Verification reads the algorithm and costs from the PHC string itself.
 pub async fn verify(password: String, hash: String) -> Result<bool, BoxError> {
+    let _permit = PERMITS.acquire().await?;
     task::spawn_blocking(move || {
         let hash = PasswordHash::new(&hash)?;
-        match Argon2::default().verify_password(password.as_bytes(), &hash) {
+        match hasher().verify_password(password.as_bytes(), &hash) {
             Ok(()) => Ok(true),
             Err(password_hash::Error::Password) => Ok(false),
             Err(e) => Err(e.into()),
@@ -27,3 +51,50 @@ pub async fn verify(password: String, hash: String) -> Result<bool, BoxError> {
     })
     .await?
 }
+
+// Spends the same time as `verify` against a hash made with the current parameters, for
+// callers that have no stored hash but must not reveal that through their response time.
+pub async fn verify_dummy(password: String) -> Result<(), BoxError> {
+    let _permit = PERMITS.acquire().await?;
+    task::spawn_blocking(move || {
+        let hash = PasswordHash::new(dummy_hash()?)?;
+        let _ = hasher().verify_password(password.as_bytes(), &hash);
+        Ok(())
+    })
+    .await?
+}
+
+// True when the stored hash was made with another algorithm, version or cost than the
+// current configuration, so it should be replaced after the next successful verify.
+pub fn needs_rehash(hash: &str) -> bool {
+    let Ok(hash) = PasswordHash::new(hash) else {
+        return true;
+    };
+    let Ok(params) = Params::try_from(&hash) else {
+        return true;
+    };
+    let current = params_or_default();
+    hash.algorithm != Algorithm::Argon2id.ident()
+        || hash.version != Some(Version::V0x13.into())
+        || params.m_cost() != current.m_cost()
+        || params.t_cost() != current.t_cost()
+        || params.p_cost() != current.p_cost()
+}
+
+// Created once, by `configure` when it is called, so the first lookup does not take longer.
+fn dummy_hash() -> Result<&'static str, BoxError> {
+    if let Some(hash) = DUMMY_HASH.get() {
+        return Ok(hash);
+    }
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = hasher().hash_password(b"", &salt)?.to_string();
+    Ok(DUMMY_HASH.get_or_init(|| hash))
+}
+
+fn hasher() -> Argon2<'static> {
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params_or_default())
+}
+
+fn params_or_default() -> Params {
+    PARAMS.get().cloned().unwrap_or_default()
+}
@@ -0,0 +1,170 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::errors::{Error, ErrorKind};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+    EllipticCurveKeyType, Jwk, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType,
+    PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use ring::rand::SystemRandom;
+use ring::rsa::PublicKeyComponents;
+use ring::signature::{self, EcdsaKeyPair, Ed25519KeyPair, KeyPair, RsaKeyPair};
+use sha2::{Digest, Sha256};
+
+const DEFAULT_SECRET_KID: &str = "default";
+
+pub struct SigningKey {
+    pub(crate) algorithm: Algorithm,
+    pub(crate) kid: String,
+    pub(crate) encoding: EncodingKey,
+    pub(crate) decoding: DecodingKey,
+    jwk: Option<Jwk>,
+}
+
+impl SigningKey {
+    pub fn from_secret(
+        algorithm: Algorithm,
+        secret: &str,
+        kid: Option<String>,
+    ) -> Result<Self, Error> {
+        if !matches!(
+            algorithm,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(ErrorKind::InvalidAlgorithm.into());
+        }
+        Ok(Self {
+            algorithm,
+            kid: kid.unwrap_or_else(|| DEFAULT_SECRET_KID.to_string()),
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            jwk: None,
+        })
+    }
+
+    // The public half is derived from the private key, so only the private key PEM
+    // (PKCS#8, or PKCS#1 for RSA) has to be configured.
+    pub fn from_pem(algorithm: Algorithm, pem: &[u8], kid: Option<String>) -> Result<Self, Error> {
+        let der = pem::parse(pem).map_err(|_| Error::from(ErrorKind::InvalidKeyFormat))?;
+        let (encoding, params) = match algorithm {
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => {
+                let pair = if der.tag() == "RSA PRIVATE KEY" {
+                    RsaKeyPair::from_der(der.contents())
+                } else {
+                    RsaKeyPair::from_pkcs8(der.contents())
+                }
+                .map_err(|e| Error::from(ErrorKind::InvalidRsaKey(e.to_string())))?;
+                let public = PublicKeyComponents::<Vec<u8>>::from(pair.public());
+                let params = AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(&public.n),
+                    e: URL_SAFE_NO_PAD.encode(&public.e),
+                });
+                (EncodingKey::from_rsa_pem(pem)?, params)
+            }
+            Algorithm::ES256 | Algorithm::ES384 => {
+                let (signing, curve) = if algorithm == Algorithm::ES256 {
+                    (
+                        &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+                        EllipticCurve::P256,
+                    )
+                } else {
+                    (
+                        &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+                        EllipticCurve::P384,
+                    )
+                };
+                let pair = EcdsaKeyPair::from_pkcs8(signing, der.contents(), &SystemRandom::new())
+                    .map_err(|_| Error::from(ErrorKind::InvalidEcdsaKey))?;
+                // Uncompressed SEC1 point: 0x04 || x || y
+                let point = &pair.public_key().as_ref()[1..];
+                let (x, y) = point.split_at(point.len() / 2);
+                let params = AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                    key_type: EllipticCurveKeyType::EC,
+                    curve,
+                    x: URL_SAFE_NO_PAD.encode(x),
+                    y: URL_SAFE_NO_PAD.encode(y),
+                });
+                (EncodingKey::from_ec_pem(pem)?, params)
+            }
+            Algorithm::EdDSA => {
+                let pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der.contents())
+                    .map_err(|_| Error::from(ErrorKind::InvalidKeyFormat))?;
+                let params = AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(pair.public_key().as_ref()),
+                });
+                (EncodingKey::from_ed_pem(pem)?, params)
+            }
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                return Err(ErrorKind::InvalidAlgorithm.into());
+            }
+        };
+
+        let kid = kid.unwrap_or_else(|| thumbprint(&params));
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(format!("{algorithm:?}").parse::<KeyAlgorithm>()?),
+                key_id: Some(kid.clone()),
+                ..Default::default()
+            },
+            algorithm: params,
+        };
+
+        Ok(Self {
+            algorithm,
+            kid,
+            encoding,
+            decoding: DecodingKey::from_jwk(&jwk)?,
+            jwk: Some(jwk),
+        })
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn kid(&self) -> &str {
+        &self.kid
+    }
+
+    pub fn jwk(&self) -> Option<&Jwk> {
+        self.jwk.as_ref()
+    }
+}
+
+// RFC 7638 JWK thumbprint: SHA-256 over the required members in lexicographic order.
+fn thumbprint(params: &AlgorithmParameters) -> String {
+    let canonical = match params {
+        AlgorithmParameters::RSA(p) => format!(r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#, p.e, p.n),
+        AlgorithmParameters::EllipticCurve(p) => format!(
+            r#"{{"crv":"{}","kty":"EC","x":"{}","y":"{}"}}"#,
+            curve_name(&p.curve),
+            p.x,
+            p.y
+        ),
+        AlgorithmParameters::OctetKeyPair(p) => format!(
+            r#"{{"crv":"{}","kty":"OKP","x":"{}"}}"#,
+            curve_name(&p.curve),
+            p.x
+        ),
+        AlgorithmParameters::OctetKey(p) => format!(r#"{{"k":"{}","kty":"oct"}}"#, p.value),
+    };
+    URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
+
+fn curve_name(curve: &EllipticCurve) -> &'static str {
+    match curve {
+        EllipticCurve::P256 => "P-256",
+        EllipticCurve::P384 => "P-384",
+        EllipticCurve::P521 => "P-521",
+        EllipticCurve::Ed25519 => "Ed25519",
+    }
+}
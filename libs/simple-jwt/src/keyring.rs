@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use jsonwebtoken::jwk::JwkSet;
+
+use crate::SigningKey;
+
+struct ScheduledKey {
+    key: SigningKey,
+    activate_at: Option<DateTime<Utc>>,
+    retire_at: Option<DateTime<Utc>>,
+}
+
+impl ScheduledKey {
+    fn is_retired(&self, now: DateTime<Utc>) -> bool {
+        self.retire_at.is_some_and(|t| t <= now)
+    }
+
+    fn is_active(&self, now: DateTime<Utc>) -> bool {
+        !self.is_retired(now) && self.activate_at.is_none_or(|t| t <= now)
+    }
+}
+
+// Tokens are signed with the most recently activated key; every key that has not
+// retired yet (including ones scheduled for later) is accepted and published, so
+// verifiers can pick up a new key before it starts signing.
+#[derive(Default)]
+pub struct KeyRing {
+    keys: Vec<ScheduledKey>,
+}
+
+impl KeyRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns false (and keeps the existing key) if the kid is already in the ring.
+    pub fn insert(
+        &mut self,
+        key: SigningKey,
+        activate_at: Option<DateTime<Utc>>,
+        retire_at: Option<DateTime<Utc>>,
+    ) -> bool {
+        if self.keys.iter().any(|k| k.key.kid == key.kid) {
+            return false;
+        }
+        self.keys.push(ScheduledKey {
+            key,
+            activate_at,
+            retire_at,
+        });
+        true
+    }
+
+    pub fn active(&self, now: DateTime<Utc>) -> Option<&SigningKey> {
+        self.keys
+            .iter()
+            .filter(|k| k.is_active(now))
+            .reduce(|a, b| if b.activate_at > a.activate_at { b } else { a })
+            .map(|k| &k.key)
+    }
+
+    pub fn find(&self, kid: &str, now: DateTime<Utc>) -> Option<&SigningKey> {
+        self.accepted(now).find(|k| k.kid == kid)
+    }
+
+    pub fn accepted(&self, now: DateTime<Utc>) -> impl Iterator<Item = &SigningKey> {
+        self.keys
+            .iter()
+            .filter(move |k| !k.is_retired(now))
+            .map(|k| &k.key)
+    }
+
+    pub fn jwks(&self, now: DateTime<Utc>) -> JwkSet {
+        JwkSet {
+            keys: self
+                .accepted(now)
+                .filter_map(|k| k.jwk().cloned())
+                .collect(),
+        }
+    }
+}
+
+impl From<SigningKey> for KeyRing {
+    fn from(key: SigningKey) -> Self {
+        let mut ring = Self::new();
+        ring.insert(key, None, None);
+        ring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Algorithm, Claims};
+    use chrono::Duration;
+
+    const ISSUER: &str = "keyring-test";
+
+    fn key(kid: &str) -> SigningKey {
+        SigningKey::from_secret(Algorithm::HS256, &format!("{kid}-secret"), Some(kid.into()))
+            .unwrap()
+    }
+
+    fn kid_of(token: &str) -> String {
+        jsonwebtoken::decode_header(token).unwrap().kid.unwrap()
+    }
+
+    #[test]
+    fn the_most_recently_activated_key_signs() {
+        let now = Utc::now();
+        let mut ring = KeyRing::from(key("old"));
+        assert!(ring.insert(key("new"), Some(now - Duration::minutes(1)), None));
+        assert!(ring.insert(key("next"), Some(now + Duration::days(1)), None));
+
+        assert_eq!(ring.active(now).unwrap().kid(), "new");
+        assert_eq!(ring.active(now + Duration::days(2)).unwrap().kid(), "next");
+        // Scheduled keys are accepted before they start signing.
+        let accepted: Vec<&str> = ring.accepted(now).map(|k| k.kid()).collect();
+        assert_eq!(accepted, ["old", "new", "next"]);
+    }
+
+    #[test]
+    fn a_kid_is_only_inserted_once() {
+        let mut ring = KeyRing::from(key("only"));
+        assert!(!ring.insert(key("only"), Some(Utc::now()), None));
+        assert_eq!(ring.accepted(Utc::now()).count(), 1);
+    }
+
+    #[test]
+    fn tokens_of_the_previous_key_verify_until_it_retires() {
+        let now = Utc::now();
+        let claims = Claims::new("member", ISSUER, 60);
+        let before = crate::encode(&claims, &KeyRing::from(key("old"))).unwrap();
+        assert_eq!(kid_of(&before), "old");
+
+        let mut rotated = KeyRing::new();
+        rotated.insert(key("old"), None, Some(now + Duration::hours(1)));
+        rotated.insert(key("new"), Some(now - Duration::minutes(1)), None);
+        let after = crate::encode(&claims, &rotated).unwrap();
+        assert_eq!(kid_of(&after), "new");
+        assert_eq!(crate::decode(&before, ISSUER, &rotated).unwrap(), claims);
+        assert_eq!(crate::decode(&after, ISSUER, &rotated).unwrap(), claims);
+
+        let mut retired = KeyRing::new();
+        retired.insert(key("old"), None, Some(now - Duration::minutes(1)));
+        retired.insert(key("new"), Some(now - Duration::hours(1)), None);
+        assert!(crate::decode(&before, ISSUER, &retired).is_err());
+        assert!(crate::decode(&after, ISSUER, &retired).is_ok());
+    }
+
+    #[test]
+    fn tokens_with_an_unknown_kid_are_rejected() {
+        let claims = Claims::new("member", ISSUER, 60);
+        let token = crate::encode(&claims, &KeyRing::from(key("elsewhere"))).unwrap();
+        assert!(crate::decode(&token, ISSUER, &KeyRing::from(key("here"))).is_err());
+    }
+}
@@ -1,6 +1,13 @@
 use chrono::{DateTime, Utc};
-use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
-use serde::{Deserialize, Serialize};
+use jsonwebtoken::{Header, Validation, errors::ErrorKind};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+mod key;
+mod keyring;
+pub use jsonwebtoken::Algorithm;
+pub use jsonwebtoken::jwk::{Jwk, JwkSet};
+pub use key::SigningKey;
+pub use keyring::KeyRing;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Claims {
@@ -9,6 +16,10 @@ pub struct Claims {
     pub iat: i64,    // Issued At (発行日時)
     pub exp: i64,    // expiration time (トークンの有効期限)
     pub jti: String, // JWT ID (JWTの一意な識別子)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat_us: Option<i64>, // Issued At (マイクロ秒、同一秒内の失効判定用)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>, // roles (ユーザーのロール)
 }
 
 impl Claims {
@@ -20,28 +31,87 @@ impl Claims {
             iat: current_time.timestamp(),
             exp: current_time.timestamp() + duration_seconds,
             jti: uuid::Uuid::new_v4().to_string(),
+            iat_us: Some(current_time.timestamp_micros()),
+            roles: vec![],
         }
     }
+
+    pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = roles;
+        self
+    }
+}
+
+pub fn encode<T: Serialize>(
+    claims: &T,
+    keys: &KeyRing,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let key = keys.active(Utc::now()).ok_or(ErrorKind::InvalidKeyFormat)?;
+    let mut header = Header::new(key.algorithm);
+    header.kid = Some(key.kid.clone());
+    jsonwebtoken::encode(&header, &claims, &key.encoding)
+}
+
+// Tokens carrying an `aud` claim are rejected, so tokens issued for other audiences
+// (see `decode_for`) are never accepted in place of these.
+pub fn decode(
+    token: &str,
+    iss: &str,
+    keys: &KeyRing,
+) -> Result<Claims, jsonwebtoken::errors::Error> {
+    decode_as(token, iss, None, keys)
+}
+
+// Decodes tokens whose `aud` claim must contain `aud`.
+pub fn decode_for<T: DeserializeOwned>(
+    token: &str,
+    iss: &str,
+    aud: &str,
+    keys: &KeyRing,
+) -> Result<T, jsonwebtoken::errors::Error> {
+    decode_as(token, iss, Some(aud), keys)
 }
 
-pub fn encode(claims: &Claims, secret: &str) -> Result<String, jsonwebtoken::errors::Error> {
-    Ok(jsonwebtoken::encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.to_string().as_bytes()),
-    )?)
+// Tokens without a kid predate key ids, so they are tried against every accepted key.
+fn decode_as<T: DeserializeOwned>(
+    token: &str,
+    iss: &str,
+    aud: Option<&str>,
+    keys: &KeyRing,
+) -> Result<T, jsonwebtoken::errors::Error> {
+    let now = Utc::now();
+    match jsonwebtoken::decode_header(token)?.kid {
+        Some(kid) => match keys.find(&kid, now) {
+            Some(key) => decode_with(token, iss, aud, key),
+            None => Err(ErrorKind::InvalidToken.into()),
+        },
+        None => {
+            let mut result = Err(ErrorKind::InvalidToken.into());
+            for key in keys.accepted(now) {
+                result = decode_with(token, iss, aud, key);
+                if result.is_ok() {
+                    break;
+                }
+            }
+            result
+        }
+    }
 }
 
-pub fn decode(token: &str, iss: &str, secret: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let mut validation = Validation::default();
+fn decode_with<T: DeserializeOwned>(
+    token: &str,
+    iss: &str,
+    aud: Option<&str>,
+    key: &SigningKey,
+) -> Result<T, jsonwebtoken::errors::Error> {
+    let mut validation = Validation::new(key.algorithm);
     validation.leeway = 30;
     validation.validate_exp = true;
     validation.set_issuer(&[iss]);
-    let claims: Claims = jsonwebtoken::decode::<Claims>(
-        &token,
-        &DecodingKey::from_secret(secret.to_string().as_ref()),
-        &validation,
-    )?
-    .claims;
+    if let Some(aud) = aud {
+        validation.set_audience(&[aud]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+    }
+    let claims: T = jsonwebtoken::decode::<T>(token, &key.decoding, &validation)?.claims;
     Ok(claims)
 }
@@ -0,0 +1,114 @@
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+
+// RFC 6238 parameters understood by every common authenticator app.
+pub const DIGITS: usize = 6;
+pub const STEP: u64 = 30;
+const SKEW: u64 = 1;
+const SECRET_BYTES: usize = 20;
+const RECOVERY_CODE_BYTES: usize = 7;
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+pub fn generate_secret() -> String {
+    encode_base32(&random_bytes(SECRET_BYTES))
+}
+
+// 10 base32 characters (50 bits) shown as "xxxxx-xxxxx".
+pub fn generate_recovery_code() -> String {
+    let code = encode_base32(&random_bytes(RECOVERY_CODE_BYTES)).to_lowercase();
+    format!("{}-{}", &code[..5], &code[5..10])
+}
+
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let issuer = percent_encode(issuer);
+    format!(
+        "otpauth://totp/{issuer}:{}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={DIGITS}&period={STEP}",
+        percent_encode(account)
+    )
+}
+
+pub fn code_at(secret: &str, unix_time: u64) -> Option<String> {
+    let key = decode_base32(secret)?;
+    Some(hotp(&key, unix_time / STEP))
+}
+
+// Accepts one step of clock drift either way and returns the matching time step,
+// so callers can refuse a code whose step was already used.
+pub fn verify(secret: &str, code: &str, unix_time: u64) -> Option<u64> {
+    if code.len() != DIGITS || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let key = decode_base32(secret)?;
+    let current = unix_time / STEP;
+    (current.saturating_sub(SKEW)..=current + SKEW)
+        .find(|&step| constant_time_eq(hotp(&key, step).as_bytes(), code.as_bytes()))
+}
+
+fn hotp(key: &[u8], counter: u64) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, key);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let digest = tag.as_ref();
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    format!("{:0DIGITS$}", value % 10u32.pow(DIGITS as u32))
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random generator failed");
+    bytes
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn decode_base32(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.bytes().filter(|c| !matches!(c, b'=' | b' ' | b'-')) {
+        let value = BASE32.iter().position(|&b| b == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    (!out.is_empty()).then_some(out)
+}
+
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS account_revocation;
+
+DROP TABLE IF EXISTS revoked_token;
+
+DROP TABLE IF EXISTS refresh_token;
+
+DROP TABLE IF EXISTS todo;
+
+DROP TABLE IF EXISTS member;
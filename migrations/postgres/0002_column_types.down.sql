@@ -0,0 +1,6 @@
+ALTER SEQUENCE todo_id_seq AS INTEGER;
+
+ALTER TABLE todo
+    ALTER COLUMN id TYPE INTEGER,
+    ALTER COLUMN due_date TYPE TIMESTAMP USING due_date AT TIME ZONE 'UTC',
+    ALTER COLUMN complete DROP NOT NULL;
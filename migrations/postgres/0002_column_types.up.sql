@@ -0,0 +1,8 @@
+UPDATE todo SET complete = FALSE WHERE complete IS NULL;
+
+ALTER TABLE todo
+    ALTER COLUMN id TYPE BIGINT,
+    ALTER COLUMN due_date TYPE TIMESTAMPTZ USING due_date AT TIME ZONE 'UTC',
+    ALTER COLUMN complete SET NOT NULL;
+
+ALTER SEQUENCE todo_id_seq AS BIGINT;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS member_role;
+
+ALTER TABLE member DROP COLUMN disabled;
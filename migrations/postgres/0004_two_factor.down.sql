@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS signin_challenge;
+
+DROP TABLE IF EXISTS recovery_code;
+
+DROP TABLE IF EXISTS member_totp;
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS login_attempt (
+    scope TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    failures BIGINT NOT NULL DEFAULT 0,
+    blocked_until TIMESTAMPTZ,
+    last_failed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (scope, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_attempt_last_failed_at ON login_attempt (last_failed_at);
@@ -0,0 +1,4 @@
+ALTER TABLE member
+    DROP COLUMN verification_sent_at,
+    DROP COLUMN verified,
+    DROP COLUMN email;
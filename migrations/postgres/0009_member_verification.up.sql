@@ -0,0 +1,4 @@
+ALTER TABLE member
+    ADD COLUMN email TEXT,
+    ADD COLUMN verified BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN verification_sent_at TIMESTAMPTZ;
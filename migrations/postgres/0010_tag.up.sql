@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS tag (
+    id BIGSERIAL PRIMARY KEY,
+    account TEXT NOT NULL,
+    name TEXT NOT NULL,
+    UNIQUE (account, name)
+);
+
+CREATE TABLE IF NOT EXISTS todo_tag (
+    todo_id BIGINT NOT NULL,
+    tag_id BIGINT NOT NULL,
+    PRIMARY KEY (todo_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_tag_tag_id ON todo_tag (tag_id, todo_id);
@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS checklist_item;
+
+DROP INDEX IF EXISTS idx_todo_parent_id;
+
+ALTER TABLE todo
+    DROP COLUMN parent_id,
+    DROP COLUMN priority;
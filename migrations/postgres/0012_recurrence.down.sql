@@ -0,0 +1,2 @@
+ALTER TABLE todo DROP COLUMN recurrence;
+ALTER TABLE member DROP COLUMN timezone;
@@ -0,0 +1,41 @@
+CREATE TABLE IF NOT EXISTS `member` (
+    `account` TEXT NOT NULL PRIMARY KEY,
+    `password` TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS `todo` (
+    `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+    `account` TEXT NOT NULL,
+    `due_date` TIMESTAMP NOT NULL,
+    `content` TEXT NOT NULL,
+    `complete` BOOLEAN,
+    `public` BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS `idx_todo_account_due_date` ON `todo` (`account`, `due_date`, `id`);
+
+CREATE INDEX IF NOT EXISTS `idx_todo_due_date` ON `todo` (`due_date`, `id`);
+
+CREATE TABLE IF NOT EXISTS `refresh_token` (
+    `token_hash` TEXT NOT NULL PRIMARY KEY,
+    `family` TEXT NOT NULL,
+    `account` TEXT NOT NULL,
+    `expires_at` TIMESTAMP NOT NULL,
+    `used` BOOLEAN NOT NULL DEFAULT FALSE,
+    `revoked` BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS `idx_refresh_token_family` ON `refresh_token` (`family`);
+
+CREATE INDEX IF NOT EXISTS `idx_refresh_token_account` ON `refresh_token` (`account`);
+
+CREATE TABLE IF NOT EXISTS `revoked_token` (
+    `jti` TEXT NOT NULL PRIMARY KEY,
+    `account` TEXT NOT NULL,
+    `expires_at` TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS `account_revocation` (
+    `account` TEXT NOT NULL PRIMARY KEY,
+    `revoked_at` TIMESTAMP NOT NULL
+);
@@ -0,0 +1,9 @@
+ALTER TABLE `member` ADD COLUMN `disabled` BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS `member_role` (
+    `account` TEXT NOT NULL,
+    `role` TEXT NOT NULL,
+    PRIMARY KEY (`account`, `role`)
+);
+
+INSERT INTO `member_role` (`account`, `role`) SELECT `account`, 'member' FROM `member`;
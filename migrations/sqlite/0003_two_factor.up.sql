@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS `member_totp` (
+    `account` TEXT NOT NULL PRIMARY KEY,
+    `secret` TEXT NOT NULL,
+    `confirmed` BOOLEAN NOT NULL DEFAULT FALSE,
+    `last_step` INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS `recovery_code` (
+    `account` TEXT NOT NULL,
+    `code_hash` TEXT NOT NULL,
+    `used` BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (`account`, `code_hash`)
+);
+
+CREATE TABLE IF NOT EXISTS `signin_challenge` (
+    `token_hash` TEXT NOT NULL PRIMARY KEY,
+    `account` TEXT NOT NULL,
+    `expires_at` TIMESTAMP NOT NULL,
+    `attempts` INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS `login_attempt` (
+    `scope` TEXT NOT NULL,
+    `subject` TEXT NOT NULL,
+    `failures` INTEGER NOT NULL DEFAULT 0,
+    `blocked_until` TIMESTAMP,
+    `last_failed_at` TIMESTAMP NOT NULL,
+    PRIMARY KEY (`scope`, `subject`)
+);
+
+CREATE INDEX IF NOT EXISTS `idx_login_attempt_last_failed_at` ON `login_attempt` (`last_failed_at`);
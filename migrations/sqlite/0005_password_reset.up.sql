@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS `password_reset` (
+    `token_hash` TEXT NOT NULL PRIMARY KEY,
+    `account` TEXT NOT NULL,
+    `expires_at` TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS `idx_password_reset_account` ON `password_reset` (`account`);
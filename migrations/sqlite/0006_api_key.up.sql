@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS `api_key` (
+    `id` TEXT NOT NULL PRIMARY KEY,
+    `account` TEXT NOT NULL,
+    `name` TEXT NOT NULL,
+    `key_hash` TEXT NOT NULL UNIQUE,
+    `scopes` TEXT NOT NULL,
+    `created_at` TIMESTAMP NOT NULL,
+    `expires_at` TIMESTAMP NOT NULL,
+    `last_used_at` TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS `idx_api_key_account` ON `api_key` (`account`);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS `oidc_authorization_code`;
+
+DROP TABLE IF EXISTS `oidc_client`;
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS `oidc_client` (
+    `client_id` TEXT NOT NULL PRIMARY KEY,
+    `name` TEXT NOT NULL,
+    `secret_hash` TEXT,
+    `redirect_uris` TEXT NOT NULL,
+    `created_at` TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS `oidc_authorization_code` (
+    `code_hash` TEXT NOT NULL PRIMARY KEY,
+    `client_id` TEXT NOT NULL,
+    `account` TEXT NOT NULL,
+    `redirect_uri` TEXT NOT NULL,
+    `scope` TEXT NOT NULL,
+    `nonce` TEXT,
+    `code_challenge` TEXT NOT NULL,
+    `expires_at` TIMESTAMP NOT NULL
+);
@@ -0,0 +1,3 @@
+ALTER TABLE `member` DROP COLUMN `verification_sent_at`;
+ALTER TABLE `member` DROP COLUMN `verified`;
+ALTER TABLE `member` DROP COLUMN `email`;
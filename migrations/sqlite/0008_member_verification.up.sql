@@ -0,0 +1,3 @@
+ALTER TABLE `member` ADD COLUMN `email` TEXT;
+ALTER TABLE `member` ADD COLUMN `verified` BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE `member` ADD COLUMN `verification_sent_at` TIMESTAMP;
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS `tag` (
+    `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+    `account` TEXT NOT NULL,
+    `name` TEXT NOT NULL,
+    UNIQUE (`account`, `name`)
+);
+
+CREATE TABLE IF NOT EXISTS `todo_tag` (
+    `todo_id` INTEGER NOT NULL,
+    `tag_id` INTEGER NOT NULL,
+    PRIMARY KEY (`todo_id`, `tag_id`)
+);
+
+CREATE INDEX IF NOT EXISTS `idx_todo_tag_tag_id` ON `todo_tag` (`tag_id`, `todo_id`);
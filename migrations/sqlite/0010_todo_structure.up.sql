@@ -0,0 +1,14 @@
+ALTER TABLE `todo` ADD COLUMN `priority` INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE `todo` ADD COLUMN `parent_id` INTEGER;
+
+CREATE INDEX IF NOT EXISTS `idx_todo_parent_id` ON `todo` (`parent_id`);
+
+CREATE TABLE IF NOT EXISTS `checklist_item` (
+    `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+    `todo_id` INTEGER NOT NULL,
+    `position` INTEGER NOT NULL,
+    `content` TEXT NOT NULL,
+    `checked` BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS `idx_checklist_item_todo_id` ON `checklist_item` (`todo_id`, `position`);
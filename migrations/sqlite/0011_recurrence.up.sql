@@ -0,0 +1,2 @@
+ALTER TABLE `member` ADD COLUMN `timezone` TEXT;
+ALTER TABLE `todo` ADD COLUMN `recurrence` TEXT;
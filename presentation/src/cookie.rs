@@ -0,0 +1,133 @@
+use application::errors::UseCaseError;
+use application::model::auth::SigninResponse;
+use axum::http::{HeaderMap, HeaderName, Method, header::SET_COOKIE};
+use axum::response::AppendHeaders;
+use axum_extra::headers::{Cookie, HeaderMapExt};
+use uuid::Uuid;
+
+pub static CSRF_HEADER: HeaderName = HeaderName::from_static("x-csrf-token");
+
+pub type SetCookies = AppendHeaders<[(HeaderName, String); 3]>;
+
+// The access token goes to every API call, the refresh token only to the endpoints that
+// rotate or revoke it, and the CSRF token has to be readable by the bundled web UI.
+const SESSION_PATH: &str = "/service";
+const REFRESH_PATH: &str = "/service/auth";
+const CSRF_PATH: &str = "/";
+
+pub fn enabled() -> bool {
+    config::CONFIG.session_cookie.enabled
+}
+
+pub fn session_token(headers: &HeaderMap) -> Option<String> {
+    get(headers, &session_name())
+}
+
+pub fn refresh_token(headers: &HeaderMap) -> Option<String> {
+    get(headers, &refresh_name())
+}
+
+// Every new session gets a new CSRF token alongside it.
+pub fn issue(tokens: &SigninResponse) -> SetCookies {
+    let jwt = &config::CONFIG.jwt;
+    let csrf = Uuid::new_v4().simple().to_string();
+    AppendHeaders([
+        (
+            SET_COOKIE,
+            build(
+                &session_name(),
+                &tokens.token,
+                SESSION_PATH,
+                jwt.expire,
+                true,
+            ),
+        ),
+        (
+            SET_COOKIE,
+            build(
+                &refresh_name(),
+                &tokens.refresh_token,
+                REFRESH_PATH,
+                jwt.refresh_expire,
+                true,
+            ),
+        ),
+        (
+            SET_COOKIE,
+            build(&csrf_name(), &csrf, CSRF_PATH, jwt.refresh_expire, false),
+        ),
+    ])
+}
+
+pub fn clear() -> SetCookies {
+    AppendHeaders([
+        (
+            SET_COOKIE,
+            build(&session_name(), "", SESSION_PATH, 0, true),
+        ),
+        (
+            SET_COOKIE,
+            build(&refresh_name(), "", REFRESH_PATH, 0, true),
+        ),
+        (SET_COOKIE, build(&csrf_name(), "", CSRF_PATH, 0, false)),
+    ])
+}
+
+// Double-submit check: a page on another site can make the browser send the cookies,
+// but it cannot read the CSRF cookie to copy it into `X-CSRF-Token`.
+pub fn verify_csrf(method: &Method, headers: &HeaderMap) -> Result<(), UseCaseError> {
+    if method.is_safe() {
+        return Ok(());
+    }
+    let expected = get(headers, &csrf_name()).ok_or(UseCaseError::Forbidden)?;
+    let actual = headers
+        .get(&CSRF_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or(UseCaseError::Forbidden)?;
+    let diff = expected
+        .bytes()
+        .zip(actual.bytes())
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+    if expected.len() != actual.len() || diff != 0 {
+        return Err(UseCaseError::Forbidden);
+    }
+    Ok(())
+}
+
+fn get(headers: &HeaderMap, name: &str) -> Option<String> {
+    if !enabled() {
+        return None;
+    }
+    headers
+        .typed_get::<Cookie>()?
+        .get(name)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn session_name() -> String {
+    config::CONFIG.session_cookie.name.clone()
+}
+
+fn refresh_name() -> String {
+    format!("{}_refresh", config::CONFIG.session_cookie.name)
+}
+
+fn csrf_name() -> String {
+    format!("{}_csrf", config::CONFIG.session_cookie.name)
+}
+
+fn build(name: &str, value: &str, path: &str, max_age: i64, http_only: bool) -> String {
+    let cfg = &config::CONFIG.session_cookie;
+    let mut cookie = format!(
+        "{name}={value}; Path={path}; Max-Age={max_age}; SameSite={}",
+        cfg.same_site
+    );
+    if http_only {
+        cookie.push_str("; HttpOnly");
+    }
+    if cfg.secure {
+        cookie.push_str("; Secure");
+    }
+    cookie
+}
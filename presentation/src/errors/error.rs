@@ -1,36 +1,181 @@
 use application::errors::UseCaseError;
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
-use serde_json::json;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use utoipa::{ToResponse, ToSchema};
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::middleware::request_id;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
 
 pub struct ApiError(UseCaseError);
 
+#[derive(Serialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+#[response(
+    description = "RFC 7807 problem details",
+    content_type = "application/problem+json"
+)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub code: String,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<BTreeMap<String, Vec<String>>>,
+}
+
 #[rustfmt::skip]
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self.0 {
+        let context = request_id::current();
+        let code = self.0.code();
+        let mut errors = None;
+        let mut retry_after = None;
+        let (status, detail) = match self.0 {
             UseCaseError::AccountIdExists => (
                 StatusCode::CONFLICT, "Account ID already exists".to_string(),
             ),
             UseCaseError::PasswordMismatch => (
                 StatusCode::BAD_REQUEST, "The entered passwords do not match".to_string(),
             ),
+            UseCaseError::TooManyAttempts(secs) => {
+                retry_after = Some(secs);
+                (StatusCode::TOO_MANY_REQUESTS, "Too many failed attempts".to_string())
+            }
             UseCaseError::BadRequest(reason) => (StatusCode::BAD_REQUEST, reason),
+            UseCaseError::OAuth(_, description) => (StatusCode::BAD_REQUEST, description),
+            UseCaseError::Validation(e) => {
+                errors = Some(field_errors(&e));
+                (StatusCode::UNPROCESSABLE_ENTITY, "Validation failed".to_string())
+            }
             UseCaseError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
+            UseCaseError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden".to_string()),
+            UseCaseError::NotFound => (StatusCode::NOT_FOUND, "The resource was not found".to_string()),
             UseCaseError::InvalidCredentials => {
                 (StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())
             }
-            UseCaseError::Infrastructure(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR, "An internal server error occurred".to_string(),
+            UseCaseError::AccountDisabled => (StatusCode::FORBIDDEN, "Account is disabled".to_string()),
+            UseCaseError::AccountNotVerified => {
+                (StatusCode::FORBIDDEN, "Email address is not verified".to_string())
+            }
+            UseCaseError::TwoFactorEnabled => (
+                StatusCode::CONFLICT, "Two-factor authentication is already enabled".to_string(),
             ),
+            UseCaseError::TwoFactorNotEnabled => (
+                StatusCode::CONFLICT, "Two-factor authentication is not enabled".to_string(),
+            ),
+            UseCaseError::Infrastructure(e) => {
+                tracing::error!(
+                    request_id = context.as_ref().map(|c| c.id.as_str()),
+                    "infrastructure error: {}", e
+                );
+                (StatusCode::INTERNAL_SERVER_ERROR, "An internal server error occurred".to_string())
+            }
         };
 
-        let body = Json(json!({ "error": error_message }));
-        (status, body).into_response()
+        let mut response = problem(status, code, detail, errors);
+        if let Some(secs) = retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
+    }
+}
+
+pub fn problem(
+    status: StatusCode,
+    code: &str,
+    detail: String,
+    errors: Option<BTreeMap<String, Vec<String>>>,
+) -> Response {
+    let context = request_id::current();
+    let body = ProblemDetails {
+        problem_type: "about:blank".to_string(),
+        title: status.canonical_reason().unwrap_or_default().to_string(),
+        status: status.as_u16(),
+        code: code.to_string(),
+        detail,
+        instance: context.as_ref().map(|c| c.path.clone()),
+        request_id: context.map(|c| c.id),
+        errors,
+    };
+    (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(body)).into_response()
+}
+
+// Router fallbacks, so unknown paths and methods answer like every other error.
+pub async fn not_found() -> Response {
+    problem(
+        StatusCode::NOT_FOUND,
+        "not_found",
+        "No resource matches the request path".to_string(),
+        None,
+    )
+}
+
+pub async fn method_not_allowed() -> Response {
+    problem(
+        StatusCode::METHOD_NOT_ALLOWED,
+        "method_not_allowed",
+        "The request method is not supported for this path".to_string(),
+        None,
+    )
+}
+
+fn field_errors(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    let mut fields = BTreeMap::new();
+    collect_field_errors(errors, "", &mut fields);
+    fields
+}
+
+// Nested DTOs are reported under paths such as `checklist[0].content`.
+fn collect_field_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    fields: &mut BTreeMap<String, Vec<String>>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = format!("{prefix}{}", camel_case(field));
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                let messages = errors
+                    .iter()
+                    .map(|e| e.message.as_ref().unwrap_or(&e.code).to_string());
+                fields.entry(path).or_default().extend(messages);
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                collect_field_errors(errors, &format!("{path}."), fields);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(errors, &format!("{path}[{index}]."), fields);
+                }
+            }
+        }
+    }
+}
+
+fn camel_case(field: &str) -> String {
+    let mut parts = field.split('_');
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
     }
+    name
 }
 
 impl From<UseCaseError> for ApiError {
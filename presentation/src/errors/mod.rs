@@ -1,2 +1,4 @@
 mod error;
-pub use error::ApiError;
+mod oauth;
+pub use error::{ApiError, ProblemDetails, method_not_allowed, not_found};
+pub use oauth::{OAuthError, OAuthErrorBody};
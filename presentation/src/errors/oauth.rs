@@ -0,0 +1,70 @@
+use application::errors::UseCaseError;
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use utoipa::{ToResponse, ToSchema};
+
+use crate::errors::ApiError;
+
+// The token and userinfo endpoints answer in the RFC 6749 / RFC 6750 format that OAuth
+// client libraries expect instead of problem details.
+pub struct OAuthError(UseCaseError);
+
+#[derive(Serialize, ToSchema, ToResponse)]
+#[response(description = "RFC 6749 error response")]
+pub struct OAuthErrorBody {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
+}
+
+impl IntoResponse for OAuthError {
+    fn into_response(self) -> Response {
+        let (status, challenge, body) = match self.0 {
+            UseCaseError::OAuth(error, description) => {
+                let (status, challenge) = if error == "invalid_client" {
+                    (StatusCode::UNAUTHORIZED, Some("Basic"))
+                } else {
+                    (StatusCode::BAD_REQUEST, None)
+                };
+                let body = OAuthErrorBody {
+                    error: error.to_string(),
+                    error_description: Some(description),
+                };
+                (status, challenge, body)
+            }
+            UseCaseError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                Some(r#"Bearer error="invalid_token""#),
+                OAuthErrorBody {
+                    error: "invalid_token".to_string(),
+                    error_description: None,
+                },
+            ),
+            e => return ApiError::from(e).into_response(),
+        };
+
+        let mut response = (
+            status,
+            [(header::CACHE_CONTROL, HeaderValue::from_static("no-store"))],
+            Json(body),
+        )
+            .into_response();
+        if let Some(challenge) = challenge {
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                HeaderValue::from_static(challenge),
+            );
+        }
+        response
+    }
+}
+
+impl From<UseCaseError> for OAuthError {
+    fn from(error: UseCaseError) -> Self {
+        Self(error)
+    }
+}
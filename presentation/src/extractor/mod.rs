@@ -0,0 +1,6 @@
+mod client_ip;
+mod path;
+mod validated;
+pub use client_ip::ClientIp;
+pub use path::Path;
+pub use validated::{ValidatedJson, ValidatedQuery};
@@ -0,0 +1,67 @@
+use application::errors::UseCaseError;
+use axum::{
+    Json,
+    extract::{FromRequest, FromRequestParts, OptionalFromRequest, Query, Request},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::errors::ApiError;
+
+pub struct ValidatedJson<T>(pub T);
+
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = <Json<T> as FromRequest<S>>::from_request(req, state)
+            .await
+            .map_err(|e| UseCaseError::BadRequest(e.body_text()))?;
+        value.validate().map_err(UseCaseError::from)?;
+        Ok(Self(value))
+    }
+}
+
+impl<T, S> OptionalFromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Option<Self>, Self::Rejection> {
+        let value = <Json<T> as OptionalFromRequest<S>>::from_request(req, state)
+            .await
+            .map_err(|e| UseCaseError::BadRequest(e.body_text()))?;
+        match value {
+            Some(Json(value)) => {
+                value.validate().map_err(UseCaseError::from)?;
+                Ok(Some(Self(value)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| UseCaseError::BadRequest(e.body_text()))?;
+        value.validate().map_err(UseCaseError::from)?;
+        Ok(Self(value))
+    }
+}
@@ -0,0 +1,150 @@
+use axum::{Extension, Json, extract::State};
+use std::sync::Arc;
+
+use crate::errors::{ApiError, ProblemDetails};
+use crate::extractor::{Path, ValidatedJson, ValidatedQuery};
+use crate::middleware::auth::AuthMember;
+use application::UseCaseModule;
+use application::model::admin::{ListMemberRequest, MemberDto, UpdateMemberRequest};
+use application::model::todo::{ListTodoRequest, TodoPage};
+
+#[utoipa::path(
+    get,
+    path = "/service/admin/members",
+    tag = "admin",
+    params(ListMemberRequest),
+    responses(
+        (status = 200, body = Vec<MemberDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn list_members(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ValidatedQuery(dto): ValidatedQuery<ListMemberRequest>,
+) -> Result<Json<Vec<MemberDto>>, ApiError> {
+    let res = usecases.admin().list_members(dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/admin/members/{account}",
+    tag = "admin",
+    params(("account" = String, Path)),
+    responses(
+        (status = 200, body = Option<MemberDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn find_member(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Path(account): Path<String>,
+) -> Result<Json<Option<MemberDto>>, ApiError> {
+    let res = usecases.admin().find_member(&account).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/service/admin/members/{account}",
+    tag = "admin",
+    params(("account" = String, Path)),
+    request_body = UpdateMemberRequest,
+    responses(
+        (status = 200, body = Option<MemberDto>),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn update_member(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(account): Path<String>,
+    ValidatedJson(dto): ValidatedJson<UpdateMemberRequest>,
+) -> Result<Json<Option<MemberDto>>, ApiError> {
+    let res = usecases
+        .admin()
+        .update_member(&guard.account, &account, dto)
+        .await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/admin/members/{account}/unlock",
+    tag = "admin",
+    params(("account" = String, Path)),
+    responses(
+        (status = 200, body = Option<MemberDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn unlock_member(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Path(account): Path<String>,
+) -> Result<Json<Option<MemberDto>>, ApiError> {
+    let res = usecases.admin().unlock_member(&account).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/admin/members/{account}",
+    tag = "admin",
+    params(("account" = String, Path)),
+    responses(
+        (status = 200, body = Option<MemberDto>),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn delete_member(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(account): Path<String>,
+) -> Result<Json<Option<MemberDto>>, ApiError> {
+    let res = usecases
+        .admin()
+        .delete_member(&guard.account, &account)
+        .await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/admin/members/{account}/todos",
+    tag = "admin",
+    params(("account" = String, Path), ListTodoRequest),
+    responses(
+        (status = 200, body = TodoPage),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn list_member_todos(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Path(account): Path<String>,
+    ValidatedQuery(dto): ValidatedQuery<ListTodoRequest>,
+) -> Result<Json<TodoPage>, ApiError> {
+    let dto = ListTodoRequest {
+        account: Some(account.clone()),
+        ..dto
+    };
+    let res = usecases.todo().list(Some(&account), dto).await?;
+    Ok(Json(res))
+}
@@ -0,0 +1,79 @@
+use axum::{Extension, Json, extract::State};
+use std::sync::Arc;
+
+use crate::errors::{ApiError, ProblemDetails};
+use crate::extractor::{Path, ValidatedJson};
+use crate::middleware::auth::AuthMember;
+use application::UseCaseModule;
+use application::model::api_key::{ApiKeyDto, CreateApiKeyRequest, CreateApiKeyResponse};
+use application::model::auth::AuthenticatedMember;
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/api-keys",
+    tag = "auth",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, body = CreateApiKeyResponse),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn create(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ValidatedJson(dto): ValidatedJson<CreateApiKeyRequest>,
+) -> Result<Json<CreateApiKeyResponse>, ApiError> {
+    let member = AuthenticatedMember {
+        account: guard.account,
+        jti: guard.jti,
+        expires_at: guard.expires_at,
+        roles: guard.roles,
+        scopes: guard.scopes,
+    };
+    let res = usecases.api_key().create(&member, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/auth/api-keys",
+    tag = "auth",
+    responses(
+        (status = 200, body = Vec<ApiKeyDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn list(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+) -> Result<Json<Vec<ApiKeyDto>>, ApiError> {
+    let res = usecases.api_key().list(&guard.account).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/auth/api-keys/{id}",
+    tag = "auth",
+    params(("id" = String, Path)),
+    responses(
+        (status = 200, body = Option<ApiKeyDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn revoke(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(id): Path<String>,
+) -> Result<Json<Option<ApiKeyDto>>, ApiError> {
+    let res = usecases.api_key().revoke(&guard.account, &id).await?;
+    Ok(Json(res))
+}
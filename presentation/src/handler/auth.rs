@@ -1,22 +1,379 @@
-use axum::{Json, extract::State};
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
 use std::sync::Arc;
 
-use crate::errors::ApiError;
+use crate::cookie;
+use crate::errors::{ApiError, ProblemDetails};
+use crate::extractor::{ClientIp, ValidatedJson, ValidatedQuery};
+use crate::middleware::auth::AuthMember;
 use application::UseCaseModule;
-use application::model::auth::{SigninRequest, SigninResponse, SignupRequest, SignupResponse};
+use application::errors::UseCaseError;
+use application::model::auth::{
+    AuthenticatedMember, ChangePasswordRequest, DeleteAccountRequest, ForgotPasswordRequest,
+    JwkSet, RefreshRequest, ResendVerificationRequest, ResetPasswordRequest, SigninRequest,
+    SigninResponse, SigninResult, SignoutRequest, SignupRequest, SignupResponse,
+    UpdateTimezoneRequest, VerifyEmailRequest,
+};
+use application::model::two_factor::TotpSigninRequest;
 
+#[utoipa::path(
+    post,
+    path = "/service/auth/signup",
+    tag = "auth",
+    request_body = SignupRequest,
+    responses(
+        (status = 200, body = SignupResponse),
+        (status = 400, response = ProblemDetails),
+        (status = 409, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    )
+)]
 pub async fn signup(
     State(usecases): State<Arc<dyn UseCaseModule>>,
-    Json(dto): Json<SignupRequest>,
+    ValidatedJson(dto): ValidatedJson<SignupRequest>,
 ) -> Result<Json<SignupResponse>, ApiError> {
     let res = usecases.auth().signup(dto).await?;
     Ok(Json(res))
 }
 
+#[utoipa::path(
+    get,
+    path = "/service/auth/verify",
+    tag = "auth",
+    params(VerifyEmailRequest),
+    responses(
+        (status = 204, description = "The email address is verified and the account can sign in"),
+        (status = 400, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    )
+)]
+pub async fn verify_email(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ValidatedQuery(dto): ValidatedQuery<VerifyEmailRequest>,
+) -> Result<StatusCode, ApiError> {
+    usecases.auth().verify_email(dto).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/verify/resend",
+    tag = "auth",
+    request_body = ResendVerificationRequest,
+    responses(
+        (status = 204, description = "A new verification mail is sent if the account is waiting for one"),
+        (status = 422, response = ProblemDetails),
+    )
+)]
+pub async fn resend_verification(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ValidatedJson(dto): ValidatedJson<ResendVerificationRequest>,
+) -> Result<StatusCode, ApiError> {
+    usecases.auth().resend_verification(dto).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/signin",
+    tag = "auth",
+    request_body = SigninRequest,
+    responses(
+        (status = 200, body = SigninResult,
+            description = "Tokens, or a challenge to complete at /service/auth/signin/totp when two-factor authentication is enabled"),
+        (status = 204, description = "Session cookies are set (`cookie: true`)"),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+        (status = 429, description = "Too many failed attempts", body = ProblemDetails,
+            content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "Seconds until the next attempt is accepted"))),
+    )
+)]
 pub async fn signin(
     State(usecases): State<Arc<dyn UseCaseModule>>,
-    Json(dto): Json<SigninRequest>,
-) -> Result<Json<SigninResponse>, ApiError> {
-    let res = usecases.auth().signin(dto).await?;
-    Ok(Json(res))
+    ClientIp(ip): ClientIp,
+    ValidatedJson(dto): ValidatedJson<SigninRequest>,
+) -> Result<Response, ApiError> {
+    let use_cookie = cookie_requested(dto.cookie)?;
+    let res = usecases.auth().signin(dto, ip).await?;
+    match res {
+        SigninResult::Tokens(tokens) if use_cookie => Ok(session_cookies(&tokens)),
+        res => Ok(Json(res).into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/signin/totp",
+    tag = "auth",
+    request_body = TotpSigninRequest,
+    responses(
+        (status = 200, body = SigninResponse),
+        (status = 204, description = "Session cookies are set (`cookie: true`)"),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+        (status = 429, description = "Too many failed attempts", body = ProblemDetails,
+            content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "Seconds until the next attempt is accepted"))),
+    )
+)]
+pub async fn signin_totp(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ClientIp(ip): ClientIp,
+    ValidatedJson(dto): ValidatedJson<TotpSigninRequest>,
+) -> Result<Response, ApiError> {
+    let use_cookie = cookie_requested(dto.cookie)?;
+    let res = usecases.auth().signin_totp(dto, ip).await?;
+    if use_cookie {
+        Ok(session_cookies(&res))
+    } else {
+        Ok(Json(res).into_response())
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/refresh",
+    tag = "auth",
+    request_body(content = Option<RefreshRequest>,
+        description = "Omitted when the refresh token is in the session cookie"),
+    responses(
+        (status = 200, body = SigninResponse),
+        (status = 204, description = "Session cookies are renewed"),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security((), ("session_cookie" = []))
+)]
+pub async fn refresh(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    headers: HeaderMap,
+    body: Option<ValidatedJson<RefreshRequest>>,
+) -> Result<Response, ApiError> {
+    if let Some(ValidatedJson(dto)) = body {
+        let res = usecases.auth().refresh(dto).await?;
+        return Ok(Json(res).into_response());
+    }
+
+    let refresh_token = cookie::refresh_token(&headers).ok_or(UseCaseError::Unauthorized)?;
+    cookie::verify_csrf(&Method::POST, &headers)?;
+    let res = usecases
+        .auth()
+        .refresh(RefreshRequest { refresh_token })
+        .await?;
+    Ok(session_cookies(&res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/signout",
+    tag = "auth",
+    request_body(content = Option<SignoutRequest>),
+    responses(
+        (status = 204),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn signout(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    headers: HeaderMap,
+    body: Option<ValidatedJson<SignoutRequest>>,
+) -> Result<Response, ApiError> {
+    let mut dto = body.map(|ValidatedJson(dto)| dto);
+    if guard.cookie && dto.is_none() {
+        dto = cookie::refresh_token(&headers).map(|token| SignoutRequest {
+            refresh_token: Some(token),
+        });
+    }
+    let member = AuthenticatedMember {
+        account: guard.account.clone(),
+        jti: guard.jti.clone(),
+        expires_at: guard.expires_at,
+        roles: guard.roles.clone(),
+        scopes: guard.scopes.clone(),
+    };
+    usecases.auth().signout(&member, dto).await?;
+    Ok(signed_out(&guard))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/signout-all",
+    tag = "auth",
+    responses(
+        (status = 204),
+        (status = 401, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn signout_all(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+) -> Result<Response, ApiError> {
+    usecases.auth().signout_all(&guard.account).await?;
+    Ok(signed_out(&guard))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/password",
+    tag = "auth",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, body = SigninResponse, description = "New tokens; every other session is signed out"),
+        (status = 204, description = "New session cookies, when signed in with them"),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+        (status = 429, description = "Too many failed attempts", body = ProblemDetails,
+            content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "Seconds until the next attempt is accepted"))),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn change_password(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ClientIp(ip): ClientIp,
+    ValidatedJson(dto): ValidatedJson<ChangePasswordRequest>,
+) -> Result<Response, ApiError> {
+    let res = usecases
+        .auth()
+        .change_password(&guard.account, dto, ip)
+        .await?;
+    if guard.cookie {
+        Ok(session_cookies(&res))
+    } else {
+        Ok(Json(res).into_response())
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/service/auth/timezone",
+    tag = "auth",
+    request_body = UpdateTimezoneRequest,
+    responses(
+        (status = 204, description = "Recurring todos are scheduled in this time zone from now on"),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn update_timezone(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ValidatedJson(dto): ValidatedJson<UpdateTimezoneRequest>,
+) -> Result<StatusCode, ApiError> {
+    usecases.auth().update_timezone(&guard.account, dto).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/password/forgot",
+    tag = "auth",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 204, description = "A reset token is sent through the notifier if the account exists"),
+        (status = 422, response = ProblemDetails),
+    )
+)]
+pub async fn forgot_password(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ValidatedJson(dto): ValidatedJson<ForgotPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    usecases.auth().forgot_password(dto).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/password/reset",
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 204),
+        (status = 400, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    )
+)]
+pub async fn reset_password(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ValidatedJson(dto): ValidatedJson<ResetPasswordRequest>,
+) -> Result<StatusCode, ApiError> {
+    usecases.auth().reset_password(dto).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/auth/me",
+    tag = "auth",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 204, description = "The account and all of its todos are deleted"),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+        (status = 429, description = "Too many failed attempts", body = ProblemDetails,
+            content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "Seconds until the next attempt is accepted"))),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn delete_me(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ClientIp(ip): ClientIp,
+    ValidatedJson(dto): ValidatedJson<DeleteAccountRequest>,
+) -> Result<Response, ApiError> {
+    usecases
+        .auth()
+        .delete_account(&guard.account, dto, ip)
+        .await?;
+    Ok(signed_out(&guard))
+}
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "auth",
+    responses(
+        (status = 200, body = Object, description = "JSON Web Key Set of the token signing keys"),
+    )
+)]
+pub async fn jwks(State(usecases): State<Arc<dyn UseCaseModule>>) -> Json<JwkSet> {
+    Json(usecases.auth().jwks())
+}
+
+fn cookie_requested(requested: bool) -> Result<bool, UseCaseError> {
+    if requested && !cookie::enabled() {
+        return Err(UseCaseError::BadRequest(
+            "session cookies are not enabled".to_string(),
+        ));
+    }
+    Ok(requested)
+}
+
+// The tokens stay out of the body so that scripts on the page never see them.
+fn session_cookies(tokens: &SigninResponse) -> Response {
+    (StatusCode::NO_CONTENT, cookie::issue(tokens)).into_response()
+}
+
+fn signed_out(guard: &AuthMember) -> Response {
+    if guard.cookie {
+        (StatusCode::NO_CONTENT, cookie::clear()).into_response()
+    } else {
+        StatusCode::NO_CONTENT.into_response()
+    }
 }
@@ -1,2 +1,7 @@
+pub mod admin;
+pub mod api_key;
 pub mod auth;
+pub mod oidc;
+pub mod tag;
 pub mod todo;
+pub mod two_factor;
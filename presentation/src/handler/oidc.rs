@@ -0,0 +1,187 @@
+use axum::{
+    Extension, Form, Json,
+    extract::{RawQuery, State, rejection::FormRejection},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use axum_extra::{
+    TypedHeader,
+    headers::{
+        Authorization,
+        authorization::{Basic, Bearer},
+    },
+};
+use std::sync::Arc;
+
+use crate::errors::{ApiError, OAuthError, OAuthErrorBody, ProblemDetails};
+use crate::extractor::{Path, ValidatedJson, ValidatedQuery};
+use crate::middleware::auth::AuthMember;
+use application::UseCaseModule;
+use application::errors::UseCaseError;
+use application::model::oidc::{
+    AuthorizeRequest, AuthorizeResponse, CreateOidcClientRequest, CreateOidcClientResponse,
+    OidcClientDto, OpenIdConfiguration, TokenRequest, TokenResponse, UserInfoResponse,
+};
+
+#[utoipa::path(
+    get,
+    path = "/.well-known/openid-configuration",
+    tag = "oidc",
+    responses(
+        (status = 200, body = OpenIdConfiguration),
+    )
+)]
+pub async fn discovery(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+) -> Result<Json<OpenIdConfiguration>, ApiError> {
+    let res = usecases.oidc().discovery()?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/oauth2/authorize",
+    tag = "oidc",
+    params(AuthorizeRequest),
+    responses(
+        (status = 302, description = "To `oidc.login_url` with the same query, or back to the client with `error`"),
+        (status = 400, response = ProblemDetails),
+    )
+)]
+pub async fn login_redirect(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    RawQuery(query): RawQuery,
+    ValidatedQuery(dto): ValidatedQuery<AuthorizeRequest>,
+) -> Result<Response, ApiError> {
+    let location = usecases
+        .oidc()
+        .login_redirect(&dto, query.as_deref().unwrap_or_default())
+        .await?;
+    Ok((StatusCode::FOUND, [(header::LOCATION, location)]).into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/oauth2/authorize",
+    tag = "oidc",
+    params(AuthorizeRequest),
+    responses(
+        (status = 200, body = AuthorizeResponse),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn authorize(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ValidatedQuery(dto): ValidatedQuery<AuthorizeRequest>,
+) -> Result<Json<AuthorizeResponse>, ApiError> {
+    let res = usecases.oidc().authorize(&guard.account, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/oauth2/token",
+    tag = "oidc",
+    request_body(content = TokenRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, body = TokenResponse),
+        (status = 400, response = OAuthErrorBody),
+        (status = 401, response = OAuthErrorBody),
+    ),
+    security((), ("client_secret_basic" = []))
+)]
+pub async fn token(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    form: Result<Form<TokenRequest>, FormRejection>,
+) -> Result<Response, OAuthError> {
+    let Form(dto) = form.map_err(|e| UseCaseError::OAuth("invalid_request", e.body_text()))?;
+    let basic = basic
+        .map(|TypedHeader(Authorization(b))| (b.username().to_string(), b.password().to_string()));
+    let res = usecases.oidc().token(basic, dto).await?;
+    Ok(([(header::CACHE_CONTROL, "no-store")], Json(res)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/oauth2/userinfo",
+    tag = "oidc",
+    responses(
+        (status = 200, body = UserInfoResponse),
+        (status = 401, response = OAuthErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn userinfo(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    bearer: Option<TypedHeader<Authorization<Bearer>>>,
+) -> Result<Json<UserInfoResponse>, OAuthError> {
+    let Some(TypedHeader(Authorization(bearer))) = bearer else {
+        return Err(UseCaseError::Unauthorized.into());
+    };
+    let res = usecases.oidc().userinfo(bearer.token()).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/admin/oidc/clients",
+    tag = "admin",
+    request_body = CreateOidcClientRequest,
+    responses(
+        (status = 200, body = CreateOidcClientResponse),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn create_client(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    ValidatedJson(dto): ValidatedJson<CreateOidcClientRequest>,
+) -> Result<Json<CreateOidcClientResponse>, ApiError> {
+    let res = usecases.oidc().create_client(dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/admin/oidc/clients",
+    tag = "admin",
+    responses(
+        (status = 200, body = Vec<OidcClientDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn list_clients(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+) -> Result<Json<Vec<OidcClientDto>>, ApiError> {
+    let res = usecases.oidc().list_clients().await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/admin/oidc/clients/{client_id}",
+    tag = "admin",
+    params(("client_id" = String, Path)),
+    responses(
+        (status = 200, body = Option<OidcClientDto>),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn delete_client(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Path(client_id): Path<String>,
+) -> Result<Json<Option<OidcClientDto>>, ApiError> {
+    let res = usecases.oidc().delete_client(&client_id).await?;
+    Ok(Json(res))
+}
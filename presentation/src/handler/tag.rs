@@ -0,0 +1,92 @@
+use axum::{Extension, Json, extract::State};
+use std::sync::Arc;
+
+use crate::errors::{ApiError, ProblemDetails};
+use crate::extractor::{Path, ValidatedJson};
+use crate::middleware::auth::AuthMember;
+use application::UseCaseModule;
+use application::model::tag::{CreateTagRequest, TagDto, UpdateTagRequest};
+
+#[utoipa::path(
+    post,
+    path = "/service/manage/tags",
+    tag = "todo",
+    request_body = CreateTagRequest,
+    responses(
+        (status = 200, body = TagDto),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn create(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ValidatedJson(dto): ValidatedJson<CreateTagRequest>,
+) -> Result<Json<TagDto>, ApiError> {
+    let res = usecases.tag().create(&guard.account, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/manage/tags",
+    tag = "todo",
+    responses(
+        (status = 200, body = Vec<TagDto>),
+        (status = 401, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn list(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+) -> Result<Json<Vec<TagDto>>, ApiError> {
+    let res = usecases.tag().list(&guard.account).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    put,
+    path = "/service/manage/tags/{id}",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    request_body = UpdateTagRequest,
+    responses(
+        (status = 200, body = Option<TagDto>),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn update(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(id): Path<i64>,
+    ValidatedJson(dto): ValidatedJson<UpdateTagRequest>,
+) -> Result<Json<Option<TagDto>>, ApiError> {
+    let res = usecases.tag().update(&guard.account, id, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/manage/tags/{id}",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    responses(
+        (status = 200, body = Option<TagDto>),
+        (status = 401, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn delete(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(id): Path<i64>,
+) -> Result<Json<Option<TagDto>>, ApiError> {
+    let res = usecases.tag().delete(&guard.account, id).await?;
+    Ok(Json(res))
+}
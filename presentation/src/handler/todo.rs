@@ -1,25 +1,166 @@
-use axum::{Extension, Json, extract::Path, extract::State};
+use axum::{Extension, Json, extract::State};
 use std::sync::Arc;
 
-use crate::errors::ApiError;
-use crate::middleware::auth::{AuthOptionMember, AuthMember};
+use crate::errors::{ApiError, ProblemDetails};
+use crate::extractor::{Path, ValidatedJson, ValidatedQuery};
+use crate::middleware::auth::{AuthMember, AuthOptionMember};
 use application::UseCaseModule;
-use application::model::todo::{CreateTodoRequest, TodoDto};
+use application::model::todo::{
+    CreateTodoRequest, ListTodoRequest, PatchTodoRequest, TodoDto, TodoPage, TodoTreeDto,
+    UpdateTodoRequest,
+};
 
+#[utoipa::path(
+    post,
+    path = "/service/manage/todo",
+    tag = "todo",
+    request_body = CreateTodoRequest,
+    responses(
+        (status = 200, body = TodoDto),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
 pub async fn create(
     State(usecases): State<Arc<dyn UseCaseModule>>,
-    Extension(_guard): Extension<AuthMember>,
-    Json(dto): Json<CreateTodoRequest>,
+    Extension(guard): Extension<AuthMember>,
+    ValidatedJson(dto): ValidatedJson<CreateTodoRequest>,
 ) -> Result<Json<TodoDto>, ApiError> {
-    let res = usecases.todo().create(dto).await?;
+    let res = usecases.todo().create(&guard.account, dto).await?;
     Ok(Json(res))
 }
 
+#[utoipa::path(
+    get,
+    path = "/service/todo/{id}",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    responses(
+        (status = 200, body = Option<TodoDto>),
+    ),
+    security((), ("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
 pub async fn find(
     State(usecases): State<Arc<dyn UseCaseModule>>,
-    Extension(_guard): Extension<AuthOptionMember>,
+    Extension(guard): Extension<AuthOptionMember>,
     Path(id): Path<i64>,
 ) -> Result<Json<Option<TodoDto>>, ApiError> {
-    let res = usecases.todo().find(id).await?;
+    let res = usecases.todo().find(guard.account.as_deref(), id).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/todo/{id}/tree",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    responses(
+        (status = 200, body = Option<TodoTreeDto>),
+    ),
+    security((), ("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn tree(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthOptionMember>,
+    Path(id): Path<i64>,
+) -> Result<Json<Option<TodoTreeDto>>, ApiError> {
+    let res = usecases.todo().tree(guard.account.as_deref(), id).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    get,
+    path = "/service/todo",
+    tag = "todo",
+    params(ListTodoRequest),
+    responses(
+        (status = 200, body = TodoPage),
+        (status = 400, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security((), ("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn list(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthOptionMember>,
+    ValidatedQuery(dto): ValidatedQuery<ListTodoRequest>,
+) -> Result<Json<TodoPage>, ApiError> {
+    let res = usecases.todo().list(guard.account.as_deref(), dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    put,
+    path = "/service/manage/todo/{id}",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    request_body = UpdateTodoRequest,
+    responses(
+        (status = 200, body = TodoDto),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 404, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn update(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(id): Path<i64>,
+    ValidatedJson(dto): ValidatedJson<UpdateTodoRequest>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let res = usecases.todo().update(&guard.account, id, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/service/manage/todo/{id}",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    request_body = PatchTodoRequest,
+    responses(
+        (status = 200, body = TodoDto),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 404, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn patch(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(id): Path<i64>,
+    ValidatedJson(dto): ValidatedJson<PatchTodoRequest>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let res = usecases.todo().patch(&guard.account, id, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/manage/todo/{id}",
+    tag = "todo",
+    params(("id" = i64, Path)),
+    responses(
+        (status = 200, body = TodoDto),
+        (status = 401, response = ProblemDetails),
+        (status = 403, response = ProblemDetails),
+        (status = 404, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("api_key" = []), ("session_cookie" = []))
+)]
+pub async fn delete(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    Path(id): Path<i64>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let res = usecases.todo().delete(&guard.account, id).await?;
     Ok(Json(res))
 }
@@ -0,0 +1,129 @@
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use std::sync::Arc;
+
+use crate::errors::{ApiError, ProblemDetails};
+use crate::extractor::{ClientIp, ValidatedJson};
+use crate::middleware::auth::AuthMember;
+use application::UseCaseModule;
+use application::model::two_factor::{
+    RecoveryCodesResponse, TotpConfirmRequest, TotpEnrollResponse, TwoFactorReauthRequest,
+    TwoFactorStatusResponse,
+};
+
+#[utoipa::path(
+    get,
+    path = "/service/auth/totp",
+    tag = "auth",
+    responses(
+        (status = 200, body = TwoFactorStatusResponse),
+        (status = 401, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn status(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+) -> Result<Json<TwoFactorStatusResponse>, ApiError> {
+    let res = usecases.two_factor().status(&guard.account).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/totp",
+    tag = "auth",
+    responses(
+        (status = 200, body = TotpEnrollResponse),
+        (status = 401, response = ProblemDetails),
+        (status = 409, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn enroll(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+) -> Result<Json<TotpEnrollResponse>, ApiError> {
+    let res = usecases.two_factor().enroll(&guard.account).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/totp/confirm",
+    tag = "auth",
+    request_body = TotpConfirmRequest,
+    responses(
+        (status = 200, body = RecoveryCodesResponse),
+        (status = 400, response = ProblemDetails),
+        (status = 401, response = ProblemDetails),
+        (status = 409, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn confirm(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ValidatedJson(dto): ValidatedJson<TotpConfirmRequest>,
+) -> Result<Json<RecoveryCodesResponse>, ApiError> {
+    let res = usecases.two_factor().confirm(&guard.account, dto).await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    post,
+    path = "/service/auth/totp/recovery-codes",
+    tag = "auth",
+    request_body = TwoFactorReauthRequest,
+    responses(
+        (status = 200, body = RecoveryCodesResponse),
+        (status = 401, response = ProblemDetails),
+        (status = 409, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+        (status = 429, description = "Too many failed attempts", body = ProblemDetails,
+            content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "Seconds until the next attempt is accepted"))),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn regenerate_recovery_codes(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ClientIp(ip): ClientIp,
+    ValidatedJson(dto): ValidatedJson<TwoFactorReauthRequest>,
+) -> Result<Json<RecoveryCodesResponse>, ApiError> {
+    let res = usecases
+        .two_factor()
+        .regenerate_recovery_codes(&guard.account, dto, ip)
+        .await?;
+    Ok(Json(res))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/service/auth/totp",
+    tag = "auth",
+    request_body = TwoFactorReauthRequest,
+    responses(
+        (status = 204),
+        (status = 401, response = ProblemDetails),
+        (status = 409, response = ProblemDetails),
+        (status = 422, response = ProblemDetails),
+        (status = 429, description = "Too many failed attempts", body = ProblemDetails,
+            content_type = "application/problem+json",
+            headers(("Retry-After" = u64, description = "Seconds until the next attempt is accepted"))),
+    ),
+    security(("bearer" = []), ("session_cookie" = []))
+)]
+pub async fn disable(
+    State(usecases): State<Arc<dyn UseCaseModule>>,
+    Extension(guard): Extension<AuthMember>,
+    ClientIp(ip): ClientIp,
+    ValidatedJson(dto): ValidatedJson<TwoFactorReauthRequest>,
+) -> Result<StatusCode, ApiError> {
+    usecases
+        .two_factor()
+        .disable(&guard.account, dto, ip)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
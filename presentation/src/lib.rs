@@ -1,4 +1,7 @@
+pub mod cookie;
 pub mod errors;
+pub mod extractor;
 pub mod middleware;
 pub mod handler;
+pub mod openapi;
 pub mod router;
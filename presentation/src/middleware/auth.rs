@@ -1,9 +1,12 @@
 use application::UseCaseModule;
+use application::errors::UseCaseError;
+use application::model::auth::{AuthenticatedMember, Role, Scope};
+use application::usecase::api_key::API_KEY_PREFIX;
 
 use axum::RequestExt;
 use axum::{
     extract::{FromRequestParts, Request, State},
-    http::{StatusCode, request::Parts},
+    http::{HeaderName, header::AUTHORIZATION, request::Parts},
     middleware::Next,
     response::Response,
 };
@@ -13,9 +16,20 @@ use axum_extra::{
 };
 use std::sync::Arc;
 
+use crate::cookie;
+use crate::errors::ApiError;
+
+pub static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
 #[derive(Clone)]
 pub struct AuthMember {
     pub account: String,
+    pub jti: String,
+    pub expires_at: i64,
+    pub roles: Vec<Role>,
+    pub scopes: Option<Vec<Scope>>,
+    // Authenticated by the session cookie rather than a header
+    pub cookie: bool,
 }
 #[derive(Clone)]
 pub struct AuthOptionMember {
@@ -26,36 +40,120 @@ impl<S> FromRequestParts<S> for AuthMember
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = ApiError;
 
     async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
         parts
             .extensions
             .get::<Self>()
             .cloned()
-            .ok_or(StatusCode::UNAUTHORIZED)
+            .ok_or(UseCaseError::Unauthorized.into())
+    }
+}
+
+impl AuthMember {
+    fn new(member: AuthenticatedMember, cookie: bool) -> Self {
+        Self {
+            account: member.account,
+            jti: member.jti,
+            expires_at: member.expires_at,
+            roles: member.roles,
+            scopes: member.scopes,
+            cookie,
+        }
     }
 }
 
+// Accepts a JWT or an API key as the bearer token, an API key in `X-Api-Key`, or the
+// session cookie when neither header is sent.
+async fn authenticate(
+    module: &Arc<dyn UseCaseModule>,
+    request: &mut Request,
+) -> Result<AuthMember, UseCaseError> {
+    if let Some(key) = request.headers().get(&API_KEY_HEADER) {
+        let key = key.to_str().map_err(|_| UseCaseError::Unauthorized)?;
+        let member = module.api_key().authenticate(key).await?;
+        return Ok(AuthMember::new(member, false));
+    }
+
+    if !request.headers().contains_key(AUTHORIZATION)
+        && let Some(token) = cookie::session_token(request.headers())
+    {
+        let member = module.auth().authenticate(&token).await?;
+        return Ok(AuthMember::new(member, true));
+    }
+
+    let bearer = request
+        .extract_parts::<TypedHeader<Authorization<Bearer>>>()
+        .await
+        .map_err(|_| UseCaseError::Unauthorized)?;
+    let token = bearer.token();
+    let member = if token.starts_with(API_KEY_PREFIX) {
+        module.api_key().authenticate(token).await?
+    } else {
+        module.auth().authenticate(token).await?
+    };
+    Ok(AuthMember::new(member, false))
+}
+
 pub async fn auth_guard(
     State(module): State<Arc<dyn UseCaseModule>>,
     mut request: Request,
     next: Next,
 ) -> axum::response::Result<Response> {
-    let bearer = request
-        .extract_parts::<TypedHeader<Authorization<Bearer>>>()
+    let member = authenticate(&module, &mut request)
         .await
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
-    let token = bearer.token();
+        .map_err(ApiError::from)?;
+    if member.cookie {
+        cookie::verify_csrf(request.method(), request.headers()).map_err(ApiError::from)?;
+    }
+    request.extensions_mut().insert(member);
 
-    let account = module
-        .auth()
-        .authenticate(token)
-        .await
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    Ok(next.run(request).await)
+}
 
-    let auth_account = AuthMember { account };
-    request.extensions_mut().insert(auth_account);
+// Requests made with an API key must carry the scope; signed-in sessions have every scope.
+pub async fn require_scope(
+    State(scope): State<Scope>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let member = request
+        .extensions()
+        .get::<AuthMember>()
+        .ok_or(ApiError::from(UseCaseError::Unauthorized))?;
+    if member.scopes.as_ref().is_some_and(|s| !s.contains(&scope)) {
+        return Err(UseCaseError::Forbidden.into());
+    }
+
+    Ok(next.run(request).await)
+}
+
+// Account and credential management stays out of reach of API keys.
+pub async fn require_session(request: Request, next: Next) -> Result<Response, ApiError> {
+    let member = request
+        .extensions()
+        .get::<AuthMember>()
+        .ok_or(ApiError::from(UseCaseError::Unauthorized))?;
+    if member.scopes.is_some() {
+        return Err(UseCaseError::Forbidden.into());
+    }
+
+    Ok(next.run(request).await)
+}
+
+pub async fn require_role(
+    State(role): State<Role>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let member = request
+        .extensions()
+        .get::<AuthMember>()
+        .ok_or(ApiError::from(UseCaseError::Unauthorized))?;
+    if !member.roles.contains(&role) {
+        return Err(UseCaseError::Forbidden.into());
+    }
 
     Ok(next.run(request).await)
 }
@@ -64,14 +162,14 @@ impl<S> FromRequestParts<S> for AuthOptionMember
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = ApiError;
 
     async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
         parts
             .extensions
             .get::<Self>()
             .cloned()
-            .ok_or(StatusCode::UNAUTHORIZED)
+            .ok_or(UseCaseError::Unauthorized.into())
     }
 }
 
@@ -82,13 +180,14 @@ pub async fn auth_option_guard(
 ) -> Response {
     let mut auth_account = AuthOptionMember { account: None };
 
-    if let Ok(bearer) = request
-        .extract_parts::<TypedHeader<Authorization<Bearer>>>()
-        .await
+    // API keys without `todo:read` only see what anonymous callers see.
+    if let Ok(member) = authenticate(&module, &mut request).await
+        && member
+            .scopes
+            .as_ref()
+            .is_none_or(|s| s.contains(&Scope::TodoRead))
     {
-        if let Ok(account) = module.auth().authenticate(bearer.token()).await {
-            auth_account.account = Some(account);
-        }
+        auth_account.account = Some(member.account);
     }
     request.extensions_mut().insert(auth_account);
     next.run(request).await
@@ -0,0 +1,176 @@
+use axum::Json;
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+use crate::cookie::CSRF_HEADER;
+use crate::errors::{OAuthErrorBody, ProblemDetails};
+use crate::handler::{admin, api_key, auth, oidc, tag, todo, two_factor};
+use crate::middleware::auth::API_KEY_HEADER;
+use application::model::admin::{MemberDto, UpdateMemberRequest};
+use application::model::api_key::{ApiKeyDto, CreateApiKeyRequest, CreateApiKeyResponse};
+use application::model::auth::{
+    ChangePasswordRequest, DeleteAccountRequest, ForgotPasswordRequest, RefreshRequest,
+    ResendVerificationRequest, ResetPasswordRequest, SigninRequest, SigninResponse, SigninResult,
+    SignoutRequest, SignupRequest, SignupResponse, UpdateTimezoneRequest,
+};
+use application::model::oidc::{
+    AuthorizeResponse, CreateOidcClientRequest, CreateOidcClientResponse, OidcClientDto,
+    OpenIdConfiguration, TokenRequest, TokenResponse, UserInfoResponse,
+};
+use application::model::tag::{CreateTagRequest, TagDto, UpdateTagRequest};
+use application::model::todo::{
+    ChecklistItem, CreateTodoRequest, PatchTodoRequest, SortOrder, TodoDto, TodoPage, TodoTreeDto,
+    UpdateTodoRequest,
+};
+use application::model::two_factor::{
+    RecoveryCodesResponse, TotpConfirmRequest, TotpEnrollResponse, TotpSigninRequest,
+    TwoFactorChallengeResponse, TwoFactorReauthRequest, TwoFactorStatusResponse,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        auth::signup,
+        auth::verify_email,
+        auth::resend_verification,
+        auth::signin,
+        auth::signin_totp,
+        auth::refresh,
+        auth::signout,
+        auth::signout_all,
+        auth::change_password,
+        auth::update_timezone,
+        auth::forgot_password,
+        auth::reset_password,
+        auth::delete_me,
+        auth::jwks,
+        two_factor::status,
+        two_factor::enroll,
+        two_factor::confirm,
+        two_factor::regenerate_recovery_codes,
+        two_factor::disable,
+        api_key::create,
+        api_key::list,
+        api_key::revoke,
+        todo::create,
+        todo::find,
+        todo::tree,
+        todo::list,
+        todo::update,
+        todo::patch,
+        todo::delete,
+        tag::create,
+        tag::list,
+        tag::update,
+        tag::delete,
+        admin::list_members,
+        admin::find_member,
+        admin::update_member,
+        admin::unlock_member,
+        admin::delete_member,
+        admin::list_member_todos,
+        oidc::create_client,
+        oidc::list_clients,
+        oidc::delete_client,
+        oidc::discovery,
+        oidc::login_redirect,
+        oidc::authorize,
+        oidc::token,
+        oidc::userinfo,
+    ),
+    components(schemas(
+        SignupRequest,
+        SignupResponse,
+        SigninRequest,
+        SigninResponse,
+        SigninResult,
+        TwoFactorChallengeResponse,
+        TotpSigninRequest,
+        TwoFactorStatusResponse,
+        TotpEnrollResponse,
+        TotpConfirmRequest,
+        RecoveryCodesResponse,
+        TwoFactorReauthRequest,
+        CreateApiKeyRequest,
+        CreateApiKeyResponse,
+        ApiKeyDto,
+        RefreshRequest,
+        SignoutRequest,
+        ChangePasswordRequest,
+        UpdateTimezoneRequest,
+        ResendVerificationRequest,
+        ForgotPasswordRequest,
+        ResetPasswordRequest,
+        DeleteAccountRequest,
+        CreateTodoRequest,
+        UpdateTodoRequest,
+        PatchTodoRequest,
+        SortOrder,
+        ChecklistItem,
+        TodoDto,
+        TodoTreeDto,
+        TodoPage,
+        CreateTagRequest,
+        UpdateTagRequest,
+        TagDto,
+        MemberDto,
+        UpdateMemberRequest,
+        CreateOidcClientRequest,
+        CreateOidcClientResponse,
+        OidcClientDto,
+        OpenIdConfiguration,
+        AuthorizeResponse,
+        TokenRequest,
+        TokenResponse,
+        UserInfoResponse,
+        OAuthErrorBody,
+        ProblemDetails,
+    ), responses(ProblemDetails, OAuthErrorBody)),
+    modifiers(&BearerSecurity),
+    info(title = "axum-sqlx-ddd-template", license(name = "MIT")),
+    tags((name = "auth"), (name = "todo"), (name = "admin"), (name = "oidc"))
+)]
+pub struct ApiDoc;
+
+struct BearerSecurity;
+
+impl Modify for BearerSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(API_KEY_HEADER.as_str()))),
+        );
+        components.add_security_scheme(
+            "session_cookie",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                config::CONFIG.session_cookie.name.clone(),
+                format!(
+                    "Set by signin with `cookie: true`; requests other than GET must also send \
+                    the `{}_csrf` cookie value in `{}`",
+                    config::CONFIG.session_cookie.name,
+                    CSRF_HEADER.as_str()
+                ),
+            ))),
+        );
+        components.add_security_scheme(
+            "client_secret_basic",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Basic).build()),
+        );
+    }
+}
+
+pub async fn document() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -2,38 +2,144 @@
 use axum::{
     Router,
     http::{HeaderValue, Method},
-    middleware::from_fn_with_state,
+    middleware::{from_fn, from_fn_with_state},
     routing::{delete, get, get_service, post, put},
 };
 use std::sync::Arc;
 use tower_http::{cors::CorsLayer, services::ServeDir};
 
-use crate::handler::{auth, todo};
-use crate::middleware::auth::{auth_guard, auth_option_guard};
+use crate::errors::{method_not_allowed, not_found};
+use crate::handler::{admin, api_key, auth, oidc, tag, todo, two_factor};
+use crate::middleware::auth::{
+    auth_guard, auth_option_guard, require_role, require_scope, require_session,
+};
+use crate::middleware::request_id::{REQUEST_ID_HEADER, request_id};
+use crate::openapi;
 use application::UseCaseModule;
+use application::model::auth::{Role, Scope};
 
 pub fn create(usecases: Arc<dyn UseCaseModule>) -> Router {
     let auth_router = Router::new()
         .route("/signup", post(auth::signup))
-        .route("/signin", post(auth::signin));
+        .route("/verify", get(auth::verify_email))
+        .route("/verify/resend", post(auth::resend_verification))
+        .route("/signin", post(auth::signin))
+        .route("/signin/totp", post(auth::signin_totp))
+        .route("/refresh", post(auth::refresh))
+        .route("/password/forgot", post(auth::forgot_password))
+        .route("/password/reset", post(auth::reset_password));
+
+    let session_router = Router::new()
+        .route("/signout", post(auth::signout))
+        .route("/signout-all", post(auth::signout_all))
+        .route("/password", post(auth::change_password))
+        .route("/timezone", put(auth::update_timezone))
+        .route("/me", delete(auth::delete_me))
+        .route(
+            "/totp",
+            get(two_factor::status)
+                .post(two_factor::enroll)
+                .delete(two_factor::disable),
+        )
+        .route("/totp/confirm", post(two_factor::confirm))
+        .route(
+            "/totp/recovery-codes",
+            post(two_factor::regenerate_recovery_codes),
+        )
+        .route("/api-keys", get(api_key::list).post(api_key::create))
+        .route("/api-keys/{id}", delete(api_key::revoke))
+        .layer(from_fn(require_session))
+        .layer(from_fn_with_state(usecases.clone(), auth_guard));
 
     let manage_router = Router::new()
         .route("/todo", post(todo::create))
+        .route(
+            "/todo/{id}",
+            put(todo::update).patch(todo::patch).delete(todo::delete),
+        )
+        .route("/tags", post(tag::create))
+        .route("/tags/{id}", put(tag::update).delete(tag::delete))
+        .layer(from_fn_with_state(Scope::TodoWrite, require_scope));
+
+    // Listing tags only needs read access; both routers share the same auth guard.
+    let manage_read_router = Router::new()
+        .route("/tags", get(tag::list))
+        .layer(from_fn_with_state(Scope::TodoRead, require_scope));
+
+    let manage_router = manage_router
+        .merge(manage_read_router)
+        .layer(from_fn_with_state(usecases.clone(), auth_guard));
+
+    let mut admin_router = Router::new()
+        .route("/members", get(admin::list_members))
+        .route(
+            "/members/{account}",
+            get(admin::find_member)
+                .patch(admin::update_member)
+                .delete(admin::delete_member),
+        )
+        .route("/members/{account}/unlock", post(admin::unlock_member))
+        .route("/members/{account}/todos", get(admin::list_member_todos));
+
+    let mut well_known_router = Router::new().route("/jwks.json", get(auth::jwks));
+
+    let mut oauth_router = Router::new();
+
+    // The OpenID Connect provider is only served once `oidc.issuer` is configured.
+    if usecases.oidc().enabled() {
+        admin_router = admin_router
+            .route(
+                "/oidc/clients",
+                get(oidc::list_clients).post(oidc::create_client),
+            )
+            .route("/oidc/clients/{client_id}", delete(oidc::delete_client));
+
+        well_known_router = well_known_router.route("/openid-configuration", get(oidc::discovery));
+
+        let consent_router = Router::new()
+            .route("/authorize", post(oidc::authorize))
+            .layer(from_fn(require_session))
+            .layer(from_fn_with_state(usecases.clone(), auth_guard));
+        oauth_router = oauth_router
+            .route("/authorize", get(oidc::login_redirect))
+            .route("/token", post(oidc::token))
+            .route("/userinfo", get(oidc::userinfo).post(oidc::userinfo))
+            .merge(consent_router);
+    }
+
+    let admin_router = admin_router
+        .layer(from_fn_with_state(Role::Admin, require_role))
+        .layer(from_fn_with_state(Scope::Admin, require_scope))
         .layer(from_fn_with_state(usecases.clone(), auth_guard));
 
     let public_router = Router::new()
+        .route("/todo", get(todo::list))
         .route("/todo/{id}", get(todo::find))
+        .route("/todo/{id}/tree", get(todo::tree))
         .layer(from_fn_with_state(usecases.clone(), auth_option_guard));
 
+    let well_known_router = well_known_router.with_state(usecases.clone());
+
     let mut app = Router::new()
-        .nest("/auth", auth_router)
+        .nest("/auth", auth_router.merge(session_router))
         .nest("/manage", manage_router)
+        .nest("/admin", admin_router)
+        .nest("/oauth2", oauth_router)
         .merge(public_router)
+        .route("/openapi.json", get(openapi::document))
+        .fallback(not_found)
         .with_state(usecases);
 
     if !config::CONFIG.server.cors.is_empty() {
         let cors = CorsLayer::new()
-            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+            .allow_methods([
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+            ])
+            .expose_headers([REQUEST_ID_HEADER.clone()])
             .allow_origin(
                 config::CONFIG
                     .server
@@ -45,11 +151,23 @@ pub fn create(usecases: Arc<dyn UseCaseModule>) -> Router {
         app = app.layer(cors);
     }
 
-    app = Router::new().nest("/service", app);
+    app = Router::new()
+        .nest("/service", app)
+        .nest("/.well-known", well_known_router)
+        .layer(from_fn(request_id));
+
+    #[cfg(feature = "swagger-ui")]
+    {
+        use utoipa_swagger_ui::{Config, SwaggerUi};
+        app = app
+            .merge(SwaggerUi::new("/service/docs").config(Config::new(["/service/openapi.json"])));
+    }
+
+    app = app.method_not_allowed_fallback(method_not_allowed);
 
     if let Some(dir) = config::CONFIG.server.static_dir.as_ref() {
         app.fallback(get_service(ServeDir::new(dir)))
     } else {
-        app
+        app.fallback(not_found)
     }
 }
@@ -1,7 +1,9 @@
-use application::UseCaseModuleImpl;
+use application::model::auth::Role;
+use application::{UseCaseModule, UseCaseModuleImpl};
 use common::{setup::init_db, types::BoxError};
-use infrastructure::UnitOfWorkProviderImpl;
+use infrastructure::{UnitOfWorkProviderImpl, clock::SystemClock};
 use presentation::router;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tracing_subscriber::{EnvFilter, fmt};
@@ -14,10 +16,27 @@ async fn main() -> Result<(), BoxError> {
     }
 
     let pool = init_db(&config::CONFIG.database.dsn.clone()).await?;
+    if config::CONFIG.database.rollback.is_some() {
+        return Ok(());
+    }
+
+    let notifier = infrastructure::notifier::create(
+        &config::CONFIG.notifier.kind,
+        config::CONFIG.notifier.file.as_deref(),
+        config::CONFIG.notifier.smtp_url.as_deref(),
+        config::CONFIG.notifier.from.as_deref(),
+    )?;
+    let usecases = Arc::new(UseCaseModuleImpl::new(
+        Arc::new(UnitOfWorkProviderImpl::new(pool)),
+        notifier,
+        Arc::new(SystemClock::new()),
+    )?);
 
-    let usecases = Arc::new(UseCaseModuleImpl::new(Arc::new(
-        UnitOfWorkProviderImpl::new(pool),
-    )));
+    if let Some(ref account) = config::CONFIG.grant_admin {
+        usecases.admin().grant_role(account, Role::Admin).await?;
+        tracing::info!("->> Granted admin role to {}", account);
+        return Ok(());
+    }
 
     let app = router::create(usecases);
 
@@ -30,9 +49,12 @@ async fn main() -> Result<(), BoxError> {
         "->> Static files served from: {}",
         config::CONFIG.server.static_dir.as_deref().unwrap_or("(none)"));
 
-    axum::serve(listener, app.into_make_service())
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
 
     Ok(())
 }
@@ -0,0 +1,132 @@
+mod support;
+
+use reqwest::{Response, StatusCode, header};
+use serde_json::{Value, json};
+use std::time::{Duration, Instant};
+use support::{MAILBOX_ARGS, PASSWORD, Server, json_body};
+
+// The parts of a response a caller could compare, without the per-request id.
+async fn observable(res: Response) -> (StatusCode, Value) {
+    let status = res.status();
+    let mut body = json_body(res).await;
+    if let Some(body) = body.as_object_mut() {
+        body.remove("requestId");
+    }
+    (status, body)
+}
+
+#[tokio::test]
+async fn signin_answers_alike_for_known_and_unknown_accounts() {
+    let server = Server::start(&[]);
+    server.member("known").await;
+
+    let mut answers = Vec::new();
+    for account in ["known", "unknown"] {
+        let res = server
+            .post("/service/auth/signin")
+            .json(&json!({ "account": account, "password": "wrong-password" }))
+            .send()
+            .await
+            .unwrap();
+        answers.push(observable(res).await);
+    }
+    assert_eq!(answers[0].0, StatusCode::UNAUTHORIZED);
+    assert_eq!(answers[0], answers[1]);
+}
+
+async fn signup(server: &Server, account: &str, email: Option<&str>) -> Response {
+    server
+        .post("/service/auth/signup")
+        .json(&json!({
+            "account": account,
+            "email": email,
+            "password": PASSWORD,
+            "confirmedPassword": PASSWORD,
+        }))
+        .send()
+        .await
+        .unwrap()
+}
+
+// Status, headers other than the date and request id, and the shape of the body.
+async fn signup_answer(res: Response) -> (StatusCode, Vec<(String, String)>, Vec<String>) {
+    let status = res.status();
+    let mut headers: Vec<(String, String)> = res
+        .headers()
+        .iter()
+        .filter(|(name, _)| *name != header::DATE && name.as_str() != "x-request-id")
+        .map(|(name, value)| (name.to_string(), value.to_str().unwrap().to_string()))
+        .collect();
+    headers.sort();
+    let body = json_body(res).await;
+    let keys = body.as_object().unwrap().keys().cloned().collect();
+    (status, headers, keys)
+}
+
+#[tokio::test]
+async fn concealed_signup_answers_alike_and_tells_the_owner() {
+    let mut args = vec!["--signup-conceal-existing"];
+    args.extend(MAILBOX_ARGS);
+    let server = Server::start(&args);
+
+    const ROUNDS: usize = 5;
+    for i in 0..ROUNDS {
+        let owner = format!("owner-{i}");
+        let email = format!("{owner}@example.com");
+        let res = signup(&server, &owner, Some(&email)).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    // Account names of the same length keep the bodies the same size.
+    let (mut fresh_time, mut taken_time) = (Duration::ZERO, Duration::ZERO);
+    for i in 0..ROUNDS {
+        let started = Instant::now();
+        let fresh = signup(&server, &format!("fresh-{i}"), Some("new@example.com")).await;
+        fresh_time += started.elapsed();
+        let started = Instant::now();
+        let taken = signup(&server, &format!("owner-{i}"), Some("new@example.com")).await;
+        taken_time += started.elapsed();
+
+        let fresh = signup_answer(fresh).await;
+        assert_eq!(fresh.0, StatusCode::OK);
+        assert_eq!(signup_answer(taken).await, fresh);
+    }
+    let (faster, slower) = (fresh_time.min(taken_time), fresh_time.max(taken_time));
+    assert!(
+        slower < faster * 2 + Duration::from_millis(100),
+        "new accounts took {fresh_time:?}, existing ones {taken_time:?}"
+    );
+
+    // The owner still signs in with the original password.
+    server.token("owner-0").await;
+    let mut recipients: Vec<String> = server
+        .mails(ROUNDS)
+        .await
+        .iter()
+        .inspect(|mail| assert_eq!(mail["subject"], "Signup attempt"))
+        .map(|mail| mail["recipient"].as_str().unwrap().to_string())
+        .collect();
+    recipients.sort();
+    let expected: Vec<String> = (0..ROUNDS)
+        .map(|i| format!("owner-{i}@example.com"))
+        .collect();
+    assert_eq!(recipients, expected);
+}
+
+#[tokio::test]
+async fn owners_without_an_email_address_are_not_notified() {
+    let mut args = vec!["--signup-conceal-existing"];
+    args.extend(MAILBOX_ARGS);
+    let server = Server::start(&args);
+
+    assert_eq!(server.signup("quiet").await.status(), StatusCode::OK);
+    assert_eq!(server.signup("quiet").await.status(), StatusCode::OK);
+    let res = signup(&server, "noisy", Some("noisy@example.com")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(server.signup("noisy").await.status(), StatusCode::OK);
+
+    // A notice for `quiet` would have been queued before the one for `noisy`.
+    let mails = server.mails(1).await;
+    assert_eq!(mails.len(), 1, "{mails:?}");
+    assert_eq!(mails[0]["recipient"], "noisy@example.com");
+}
@@ -0,0 +1,72 @@
+mod support;
+
+use reqwest::{StatusCode, header};
+use serde_json::json;
+use support::{PASSWORD, Server};
+
+#[tokio::test]
+async fn password_change_failures_are_throttled() {
+    let server = Server::start(&["--throttle-free-attempts", "1"]);
+    let token = server.member("changer").await;
+
+    for _ in 0..2 {
+        let res = server
+            .post("/service/auth/password")
+            .bearer_auth(&token)
+            .json(&json!({ "currentPassword": "wrong-password", "newPassword": "another-long-passphrase", "confirmedPassword": "another-long-passphrase" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Even the right password waits out the backoff.
+    let res = server
+        .post("/service/auth/password")
+        .bearer_auth(&token)
+        .json(&json!({ "currentPassword": PASSWORD, "newPassword": "another-long-passphrase", "confirmedPassword": "another-long-passphrase" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(res.headers().contains_key(header::RETRY_AFTER));
+
+    let res = server.signin("changer").await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn account_deletion_failures_are_throttled() {
+    let server = Server::start(&["--throttle-free-attempts", "1"]);
+    let token = server.member("leaver").await;
+
+    for _ in 0..2 {
+        let res = server
+            .delete("/service/auth/me")
+            .bearer_auth(&token)
+            .json(&json!({ "password": "wrong-password" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let res = server
+        .delete("/service/auth/me")
+        .bearer_auth(&token)
+        .json(&json!({ "password": PASSWORD }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(res.headers().contains_key(header::RETRY_AFTER));
+
+    // The account survived the attempts.
+    let res = server
+        .get("/service/manage/tags")
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
@@ -0,0 +1,53 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::json;
+use support::{Server, json_body};
+
+async fn api_key(server: &Server, token: &str, scope: &str) -> String {
+    let res = server
+        .post("/service/auth/api-keys")
+        .bearer_auth(token)
+        .json(&json!({ "name": scope, "scopes": [scope] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    json_body(res).await["key"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn tags_are_listed_with_read_scope_and_changed_with_write_scope() {
+    let server = Server::start(&[]);
+    let token = server.member("tagger").await;
+    let read = api_key(&server, &token, "todo:read").await;
+    let write = api_key(&server, &token, "todo:write").await;
+
+    let list = |key: &str| {
+        server
+            .get("/service/manage/tags")
+            .header("x-api-key", key)
+            .send()
+    };
+    let create = |key: &str, name: &str| {
+        server
+            .post("/service/manage/tags")
+            .header("x-api-key", key)
+            .json(&json!({ "name": name }))
+            .send()
+    };
+
+    assert_eq!(list(&read).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(list(&write).await.unwrap().status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        create(&read, "home").await.unwrap().status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        create(&write, "home").await.unwrap().status(),
+        StatusCode::OK
+    );
+
+    let tags = json_body(list(&read).await.unwrap()).await;
+    assert_eq!(tags[0]["name"], "home");
+}
@@ -0,0 +1,42 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::json;
+use support::Server;
+
+async fn failed_signin(server: &Server, account: &str, forwarded_for: &str) -> StatusCode {
+    server
+        .post("/service/auth/signin")
+        .header("x-forwarded-for", forwarded_for)
+        .json(&json!({ "account": account, "password": "wrong-password" }))
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn spoofed_forwarded_for_entries_do_not_change_the_throttle_key() {
+    let server = Server::start(&[
+        "--trust-proxy",
+        "--throttle-lockout-threshold",
+        "5",
+        "--throttle-ip-lockout-threshold",
+        "2",
+    ]);
+
+    // The proxy appends 203.0.113.7; the client makes up the rest.
+    for (account, forwarded_for) in [
+        ("first", "198.51.100.1, 203.0.113.7"),
+        ("second", "198.51.100.2, 203.0.113.7"),
+    ] {
+        let status = failed_signin(&server, account, forwarded_for).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    let status = failed_signin(&server, "third", "198.51.100.3, 203.0.113.7").await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    let status = failed_signin(&server, "third", "198.51.100.3, 203.0.113.8").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
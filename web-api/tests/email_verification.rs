@@ -0,0 +1,300 @@
+mod support;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use reqwest::{Response, StatusCode};
+use serde_json::{Value, json};
+use simple_jwt::{Algorithm, KeyRing, SigningKey};
+use std::time::Duration;
+use support::{MAILBOX_ARGS, PASSWORD, Server, json_body};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+fn start(extra: &[&str]) -> Server {
+    let mut args = vec!["--signup-require-verification"];
+    args.extend(MAILBOX_ARGS);
+    args.extend(extra);
+    Server::start(&args)
+}
+
+async fn signup(server: &Server, account: &str) -> Response {
+    server
+        .post("/service/auth/signup")
+        .json(&json!({
+            "account": account,
+            "password": PASSWORD,
+            "confirmedPassword": PASSWORD,
+            "email": format!("{account}@example.com"),
+        }))
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn verify(server: &Server, token: &str) -> StatusCode {
+    server
+        .get("/service/auth/verify")
+        .query(&[("token", token)])
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn resend(server: &Server, account: &str) -> StatusCode {
+    server
+        .post("/service/auth/verify/resend")
+        .json(&json!({ "account": account }))
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+fn token_in(mail: &Value) -> String {
+    mail["body"]
+        .as_str()
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix("Verification token: "))
+        .unwrap()
+        .to_string()
+}
+
+async fn signin_code(server: &Server, account: &str) -> (StatusCode, Value) {
+    let res = server.signin(account).await;
+    let status = res.status();
+    (status, json_body(res).await["code"].clone())
+}
+
+#[tokio::test]
+async fn members_sign_in_once_their_address_is_verified() {
+    let server = start(&[]);
+
+    let res = server.signup("nomail").await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    assert_eq!(signup(&server, "pending").await.status(), StatusCode::OK);
+    assert_eq!(
+        signin_code(&server, "pending").await,
+        (StatusCode::FORBIDDEN, json!("account_not_verified"))
+    );
+
+    let mails = server.mails(1).await;
+    assert_eq!(mails[0]["recipient"], "pending@example.com");
+    assert_eq!(
+        verify(&server, "not-a-token").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        verify(&server, &token_in(&mails[0])).await,
+        StatusCode::NO_CONTENT
+    );
+    server.token("pending").await;
+}
+
+#[tokio::test]
+async fn expired_tokens_are_rejected() {
+    let server = start(&[]);
+    assert_eq!(signup(&server, "late").await.status(), StatusCode::OK);
+    let token = token_in(&server.mails(1).await[0]);
+
+    // The same claims, expired an hour ago and signed with the server's secret.
+    let payload = token.split('.').nth(1).unwrap();
+    let mut claims: Value =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+    let key = SigningKey::from_secret(Algorithm::HS256, "integration-test-secret", None).unwrap();
+    let keys = KeyRing::from(key);
+    let iat = claims["iat"].as_i64().unwrap();
+    claims["iat"] = json!(iat - 7200);
+    claims["exp"] = json!(iat - 3600);
+    let expired = simple_jwt::encode(&claims, &keys).unwrap();
+
+    assert_eq!(verify(&server, &expired).await, StatusCode::BAD_REQUEST);
+    assert_eq!(signin_code(&server, "late").await.0, StatusCode::FORBIDDEN);
+
+    // Re-signed with a later expiry, the same claims are accepted.
+    claims["exp"] = json!(iat + 3600);
+    let renewed = simple_jwt::encode(&claims, &keys).unwrap();
+    assert_eq!(verify(&server, &renewed).await, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn verification_mails_can_be_resent_until_verified() {
+    let server = start(&["--signup-resend-interval", "0"]);
+    assert_eq!(signup(&server, "again").await.status(), StatusCode::OK);
+    server.mails(1).await;
+
+    assert_eq!(resend(&server, "again").await, StatusCode::NO_CONTENT);
+    let mails = server.mails(2).await;
+    assert_eq!(mails[1]["recipient"], "again@example.com");
+    assert_eq!(
+        verify(&server, &token_in(&mails[1])).await,
+        StatusCode::NO_CONTENT
+    );
+    server.token("again").await;
+
+    // Nothing is sent for verified or unknown accounts, but the answer is the same.
+    assert_eq!(resend(&server, "again").await, StatusCode::NO_CONTENT);
+    assert_eq!(resend(&server, "nobody").await, StatusCode::NO_CONTENT);
+    assert_eq!(signup(&server, "later").await.status(), StatusCode::OK);
+    let mails = server.mails(3).await;
+    assert_eq!(mails.len(), 3);
+    assert_eq!(mails[2]["recipient"], "later@example.com");
+}
+
+#[tokio::test]
+async fn resends_wait_for_the_interval() {
+    let server = start(&[]);
+    assert_eq!(signup(&server, "eager").await.status(), StatusCode::OK);
+    server.mails(1).await;
+
+    assert_eq!(resend(&server, "eager").await, StatusCode::NO_CONTENT);
+    assert_eq!(signup(&server, "other").await.status(), StatusCode::OK);
+    let mails = server.mails(2).await;
+    assert_eq!(mails.len(), 2);
+    assert_eq!(mails[1]["recipient"], "other@example.com");
+}
+
+// One message as an SMTP stand-in received it.
+#[derive(Debug, Default)]
+struct Envelope {
+    from: String,
+    to: Vec<String>,
+    data: String,
+}
+
+// Accepts plain SMTP on a free local port and passes every message on through the channel.
+async fn smtp_stand_in() -> (u16, mpsc::UnboundedReceiver<Envelope>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(smtp_session(stream, sender.clone()));
+        }
+    });
+    (port, receiver)
+}
+
+async fn smtp_session(stream: TcpStream, mails: mpsc::UnboundedSender<Envelope>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut mail = Envelope::default();
+    let mut reply = "220 localhost ESMTP";
+    loop {
+        if writer
+            .write_all(format!("{reply}\r\n").as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+        let Ok(Some(line)) = lines.next_line().await else {
+            return;
+        };
+        let address = || {
+            let start = line.find('<').map_or(0, |i| i + 1);
+            let end = line.rfind('>').unwrap_or(line.len());
+            line[start..end].to_string()
+        };
+        reply = match line
+            .get(..4)
+            .unwrap_or_default()
+            .to_ascii_uppercase()
+            .as_str()
+        {
+            "MAIL" => {
+                mail.from = address();
+                "250 OK"
+            }
+            "RCPT" => {
+                mail.to.push(address());
+                "250 OK"
+            }
+            "DATA" => {
+                if writer
+                    .write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n")
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if line == "." {
+                        break;
+                    }
+                    // Leading dots were doubled by the client.
+                    mail.data.push_str(line.strip_prefix('.').unwrap_or(&line));
+                    mail.data.push('\n');
+                }
+                let _ = mails.send(std::mem::take(&mut mail));
+                "250 OK"
+            }
+            "QUIT" => {
+                let _ = writer.write_all(b"221 Bye\r\n").await;
+                return;
+            }
+            _ => "250 OK",
+        };
+    }
+}
+
+#[tokio::test]
+async fn verification_links_are_mailed_over_smtp() {
+    let (port, mut mails) = smtp_stand_in().await;
+    let smtp_url = format!("smtp://127.0.0.1:{port}");
+    let server = Server::start(&[
+        "--signup-require-verification",
+        "--signup-verify-url",
+        "https://app.test/verify",
+        "--notifier",
+        "smtp",
+        "--notifier-smtp-url",
+        &smtp_url,
+        "--notifier-from",
+        "noreply@example.com",
+    ]);
+    assert_eq!(signup(&server, "smtp").await.status(), StatusCode::OK);
+
+    let mail = tokio::time::timeout(Duration::from_secs(10), mails.recv())
+        .await
+        .unwrap_or_else(|_| panic!("no mail arrived:\n{}", server.log()))
+        .unwrap();
+    assert_eq!(mail.from, "noreply@example.com");
+    assert_eq!(mail.to, ["smtp@example.com"]);
+    let (head, body) = mail.data.split_once("\n\n").unwrap();
+    assert!(head.lines().any(|h| h == "To: smtp@example.com"), "{head}");
+    assert!(
+        head.lines()
+            .any(|h| h == "Subject: Verify your email address"),
+        "{head}"
+    );
+
+    let body = decode_quoted_printable(body);
+    let link = body
+        .lines()
+        .find_map(|line| line.strip_prefix("Open "))
+        .and_then(|rest| rest.strip_suffix(" to verify it."))
+        .unwrap_or_else(|| panic!("no link in:\n{body}"));
+    let token = link.strip_prefix("https://app.test/verify?token=").unwrap();
+    assert_eq!(verify(&server, token).await, StatusCode::NO_CONTENT);
+    server.token("smtp").await;
+}
+
+// Enough of RFC 2045 for the ASCII bodies sent here.
+fn decode_quoted_printable(body: &str) -> String {
+    let joined = body.replace("=\n", "");
+    let mut decoded = Vec::with_capacity(joined.len());
+    let mut bytes = joined.bytes();
+    while let Some(b) = bytes.next() {
+        if b == b'=' {
+            let hex = [bytes.next().unwrap(), bytes.next().unwrap()];
+            let hex = std::str::from_utf8(&hex).unwrap();
+            decoded.push(u8::from_str_radix(hex, 16).unwrap());
+        } else {
+            decoded.push(b);
+        }
+    }
+    String::from_utf8(decoded).unwrap()
+}
@@ -0,0 +1,60 @@
+mod support;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::fs;
+use support::Server;
+
+fn kid_of(token: &str) -> String {
+    let header = token.split('.').next().unwrap();
+    let header: Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).unwrap()).unwrap();
+    header["kid"].as_str().unwrap().to_string()
+}
+
+async fn accepted(server: &Server, token: &str) -> bool {
+    let res = server
+        .get("/service/auth/totp")
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    res.status() == StatusCode::OK
+}
+
+// Restarts `server` with a web-api.config.yaml listing `keys` as the signing key ring.
+fn restart_with_keys(server: &mut Server, keys: &str) {
+    fs::write(
+        server.dir().join("web-api.config.yaml"),
+        format!("jwt:\n  keys:\n{keys}"),
+    )
+    .unwrap();
+    server.restart(&[]);
+}
+
+const OLD_KEY: &str = "    - kid: \"2026-09\"\n      secret: \"old-rotation-secret\"\n";
+const NEW_KEY: &str = "    - kid: \"2026-10\"\n      secret: \"new-rotation-secret\"\n      \
+                       activate_at: \"2026-01-01T00:00:00Z\"\n";
+
+#[tokio::test]
+async fn sessions_survive_a_key_rotation_until_the_old_key_retires() {
+    let mut server = Server::start(&[]);
+    server.member("rotator").await;
+
+    restart_with_keys(&mut server, OLD_KEY);
+    let before = server.token("rotator").await;
+    assert_eq!(kid_of(&before), "2026-09");
+
+    // The new key signs from now on; the old one still verifies until it retires.
+    let retiring = format!("{OLD_KEY}      retire_at: \"2999-01-01T00:00:00Z\"\n{NEW_KEY}");
+    restart_with_keys(&mut server, &retiring);
+    let after = server.token("rotator").await;
+    assert_eq!(kid_of(&after), "2026-10");
+    assert!(accepted(&server, &before).await);
+    assert!(accepted(&server, &after).await);
+
+    let retired = format!("{OLD_KEY}      retire_at: \"2026-02-01T00:00:00Z\"\n{NEW_KEY}");
+    restart_with_keys(&mut server, &retired);
+    assert!(!accepted(&server, &before).await);
+    assert!(accepted(&server, &after).await);
+}
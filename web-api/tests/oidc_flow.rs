@@ -0,0 +1,219 @@
+mod support;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use reqwest::{StatusCode, header};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use support::{Server, json_body};
+
+const ISSUER: &str = "http://issuer.test";
+const LOGIN_URL: &str = "https://login.test/signin";
+const REDIRECT_URI: &str = "https://wiki.test/callback";
+const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+struct Client {
+    id: String,
+    secret: String,
+}
+
+fn challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn authorize_query(client_id: &str, redirect_uri: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("response_type", "code".to_string()),
+        ("client_id", client_id.to_string()),
+        ("redirect_uri", redirect_uri.to_string()),
+        ("scope", "openid profile".to_string()),
+        ("state", "xyz".to_string()),
+        ("nonce", "n-0S6".to_string()),
+        ("code_challenge", challenge(VERIFIER)),
+        ("code_challenge_method", "S256".to_string()),
+    ]
+}
+
+async fn start() -> (Server, Client, String) {
+    let server = Server::start(&["--oidc-issuer", ISSUER, "--oidc-login-url", LOGIN_URL]);
+    server.member("admin").await;
+    server.grant_admin("admin");
+    let admin = server.token("admin").await;
+
+    let res = server
+        .post("/service/admin/oidc/clients")
+        .bearer_auth(&admin)
+        .json(&json!({ "name": "wiki", "redirectUris": [REDIRECT_URI] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = json_body(res).await;
+    let client = Client {
+        id: body["clientId"].as_str().unwrap().to_string(),
+        secret: body["clientSecret"].as_str().unwrap().to_string(),
+    };
+
+    let member = server.member("reader").await;
+    (server, client, member)
+}
+
+// Consents as `member` and returns the code from the redirect back to the client.
+async fn consent(server: &Server, client: &Client, member: &str) -> String {
+    let res = server
+        .post("/service/oauth2/authorize")
+        .query(&authorize_query(&client.id, REDIRECT_URI))
+        .bearer_auth(member)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let redirect_to = json_body(res).await["redirectTo"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let redirect = reqwest::Url::parse(&redirect_to).unwrap();
+    assert!(redirect_to.starts_with(REDIRECT_URI), "{redirect_to}");
+    let params: Vec<(String, String)> = redirect.query_pairs().into_owned().collect();
+    assert!(params.contains(&("state".to_string(), "xyz".to_string())));
+    params
+        .into_iter()
+        .find(|(k, _)| k == "code")
+        .map(|(_, v)| v)
+        .unwrap()
+}
+
+async fn exchange(
+    server: &Server,
+    client: &Client,
+    code: &str,
+    redirect_uri: &str,
+    verifier: &str,
+) -> (StatusCode, Value) {
+    let res = server
+        .post("/service/oauth2/token")
+        .basic_auth(&client.id, Some(&client.secret))
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("code_verifier", verifier),
+        ])
+        .send()
+        .await
+        .unwrap();
+    (res.status(), json_body(res).await)
+}
+
+#[tokio::test]
+async fn authorization_code_flow_with_pkce() {
+    let (server, client, member) = start().await;
+
+    let res = server
+        .get("/.well-known/openid-configuration")
+        .send()
+        .await
+        .unwrap();
+    let discovery = json_body(res).await;
+    assert_eq!(discovery["issuer"], ISSUER);
+    assert_eq!(
+        discovery["token_endpoint"],
+        format!("{ISSUER}/service/oauth2/token")
+    );
+
+    // The browser is sent on to the login page with the same query.
+    let res = server
+        .get("/service/oauth2/authorize")
+        .query(&authorize_query(&client.id, REDIRECT_URI))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::FOUND);
+    let location = res.headers()[header::LOCATION].to_str().unwrap();
+    assert!(location.starts_with(&format!("{LOGIN_URL}?")), "{location}");
+    assert!(location.contains(&client.id), "{location}");
+
+    let code = consent(&server, &client, &member).await;
+    let (status, tokens) = exchange(&server, &client, &code, REDIRECT_URI, VERIFIER).await;
+    assert_eq!(status, StatusCode::OK, "{tokens}");
+    assert_eq!(tokens["token_type"], "Bearer");
+    assert_eq!(tokens["scope"], "openid profile");
+    assert!(tokens["id_token"].is_string());
+
+    let res = server
+        .get("/service/oauth2/userinfo")
+        .bearer_auth(tokens["access_token"].as_str().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        json_body(res).await,
+        json!({ "sub": "reader", "preferred_username": "reader" })
+    );
+
+    // The ID token is not an access token.
+    let res = server
+        .get("/service/oauth2/userinfo")
+        .bearer_auth(tokens["id_token"].as_str().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn codes_are_bound_to_the_verifier_and_redirect_uri_and_used_once() {
+    let (server, client, member) = start().await;
+
+    let code = consent(&server, &client, &member).await;
+    let (status, body) = exchange(&server, &client, &code, REDIRECT_URI, "wrong-verifier").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+    // The failed exchange used the code up.
+    let (status, body) = exchange(&server, &client, &code, REDIRECT_URI, VERIFIER).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+
+    let code = consent(&server, &client, &member).await;
+    let (status, _) = exchange(&server, &client, &code, REDIRECT_URI, VERIFIER).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = exchange(&server, &client, &code, REDIRECT_URI, VERIFIER).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+
+    let code = consent(&server, &client, &member).await;
+    let other_uri = "https://wiki.test/other";
+    let (status, body) = exchange(&server, &client, &code, other_uri, VERIFIER).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+
+    // An unregistered redirect URI is never redirected to.
+    let res = server
+        .post("/service/oauth2/authorize")
+        .query(&authorize_query(&client.id, other_uri))
+        .bearer_auth(&member)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn duplicate_redirect_uris_are_stored_once() {
+    let server = Server::start(&["--oidc-issuer", ISSUER, "--oidc-login-url", LOGIN_URL]);
+    server.member("admin").await;
+    server.grant_admin("admin");
+    let admin = server.token("admin").await;
+
+    let other = "https://wiki.test/other";
+    let res = server
+        .post("/service/admin/oidc/clients")
+        .bearer_auth(&admin)
+        .json(&json!({ "name": "wiki", "redirectUris": [REDIRECT_URI, other, REDIRECT_URI] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = json_body(res).await;
+    assert_eq!(body["redirectUris"], json!([REDIRECT_URI, other]));
+}
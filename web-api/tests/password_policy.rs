@@ -0,0 +1,95 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use sqlx::SqlitePool;
+use std::fs;
+use support::{PASSWORD, Server, json_body};
+
+async fn signup_with(server: &Server, account: &str, password: &str) -> (StatusCode, Value) {
+    let res = server
+        .post("/service/auth/signup")
+        .json(&json!({
+            "account": account,
+            "password": password,
+            "confirmedPassword": password,
+        }))
+        .send()
+        .await
+        .unwrap();
+    let status = res.status();
+    (status, json_body(res).await)
+}
+
+async fn stored_hash(server: &Server, account: &str) -> String {
+    let pool = SqlitePool::connect(server.dsn()).await.unwrap();
+    let hash = sqlx::query_scalar("SELECT password FROM member WHERE account = ?")
+        .bind(account)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    pool.close().await;
+    hash
+}
+
+#[tokio::test]
+async fn weak_passwords_are_rejected_at_signup() {
+    let server = Server::start(&[]);
+
+    for (password, reason) in [
+        ("Pw-1", "must be at least 8 characters"),
+        ("Password123!", "is too common"),
+        ("aaaaaaaaaaaa", "is too easy to guess"),
+        ("abcdefgh12345678", "is too easy to guess"),
+    ] {
+        let (status, body) = signup_with(&server, "weak", password).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{password}");
+        assert_eq!(body["errors"]["password"], json!([reason]), "{password}");
+    }
+    let (status, _) = signup_with(&server, "strong", PASSWORD).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn the_deny_list_extends_the_built_in_list() {
+    let mut server = Server::start(&[]);
+    let (status, _) = signup_with(&server, "early", "Orbit-Pw-9").await;
+    assert_eq!(status, StatusCode::OK);
+
+    fs::write(
+        server.dir().join("deny.txt"),
+        "\n  ORBIT-PW-9 \nanother-one\n",
+    )
+    .unwrap();
+    server.restart(&["--password-deny-list", "deny.txt"]);
+    let (status, body) = signup_with(&server, "late", "Orbit-Pw-9").await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"]["password"], json!(["is too common"]));
+
+    // Only new passwords are checked; existing members still sign in.
+    let res = server
+        .post("/service/auth/signin")
+        .json(&json!({ "account": "early", "password": "Orbit-Pw-9" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn hashes_are_upgraded_to_the_current_parameters_on_signin() {
+    let mut server = Server::start(&[]);
+    server.member("upgraded").await;
+    let old = stored_hash(&server, "upgraded").await;
+    assert!(old.contains("m=1024,t=1,p=1"), "{old}");
+
+    server.restart(&["--password-hash-parallelism", "2"]);
+    assert_eq!(stored_hash(&server, "upgraded").await, old);
+    server.token("upgraded").await;
+    let new = stored_hash(&server, "upgraded").await;
+    assert!(new.contains("m=1024,t=1,p=2"), "{new}");
+
+    // The upgraded hash verifies the same password, and is not rehashed again.
+    server.token("upgraded").await;
+    assert_eq!(stored_hash(&server, "upgraded").await, new);
+}
@@ -0,0 +1,66 @@
+mod support;
+
+use reqwest::{Response, StatusCode, header};
+use support::{Server, json_body};
+
+async fn assert_problem(res: Response, status: StatusCode, code: &str) {
+    assert_eq!(res.status(), status);
+    assert_eq!(
+        res.headers()[header::CONTENT_TYPE],
+        "application/problem+json"
+    );
+    let body = json_body(res).await;
+    assert_eq!(body["status"], status.as_u16());
+    assert_eq!(body["code"], code);
+}
+
+#[tokio::test]
+async fn routing_and_extractor_errors_are_problem_details() {
+    let server = Server::start(&[]);
+    let token = server.member("prober").await;
+
+    let res = server.get("/service/todo/abc").send().await.unwrap();
+    assert_problem(res, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    let res = server
+        .delete("/service/manage/tags/abc")
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    assert_problem(res, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    let res = server
+        .post("/service/manage/todo")
+        .bearer_auth(&token)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body("{")
+        .send()
+        .await
+        .unwrap();
+    assert_problem(res, StatusCode::BAD_REQUEST, "bad_request").await;
+
+    for path in ["/service/no-such-route", "/no-such-route"] {
+        let res = server.get(path).send().await.unwrap();
+        assert_problem(res, StatusCode::NOT_FOUND, "not_found").await;
+    }
+
+    let res = server.delete("/service/todo").send().await.unwrap();
+    assert!(res.headers().contains_key(header::ALLOW));
+    assert_problem(res, StatusCode::METHOD_NOT_ALLOWED, "method_not_allowed").await;
+}
+
+#[tokio::test]
+async fn malformed_token_requests_are_oauth_errors() {
+    let server = Server::start(&["--oidc-issuer", "http://127.0.0.1"]);
+
+    let res = server
+        .post("/service/oauth2/token")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body("{}")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(json_body(res).await["error"], "invalid_request");
+}
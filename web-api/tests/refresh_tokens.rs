@@ -0,0 +1,67 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use support::{Server, json_body};
+
+async fn pair(server: &Server, account: &str) -> Value {
+    let res = server.signin(account).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    json_body(res).await
+}
+
+async fn refresh(server: &Server, refresh_token: &Value) -> (StatusCode, Value) {
+    let res = server
+        .post("/service/auth/refresh")
+        .json(&json!({ "refreshToken": refresh_token }))
+        .send()
+        .await
+        .unwrap();
+    let status = res.status();
+    (status, json_body(res).await)
+}
+
+#[tokio::test]
+async fn refresh_tokens_rotate_on_use() {
+    let server = Server::start(&[]);
+    server.member("rotor").await;
+    let first = pair(&server, "rotor").await;
+
+    let (status, second) = refresh(&server, &first["refreshToken"]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(second["refreshToken"], first["refreshToken"]);
+    assert_ne!(second["token"], first["token"]);
+
+    let (status, third) = refresh(&server, &second["refreshToken"]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(third["refreshToken"], second["refreshToken"]);
+}
+
+#[tokio::test]
+async fn a_replayed_refresh_token_revokes_its_whole_family() {
+    let server = Server::start(&[]);
+    server.member("victim").await;
+    let first = pair(&server, "victim").await;
+    let other = pair(&server, "victim").await;
+
+    let (status, second) = refresh(&server, &first["refreshToken"]).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The rotated token comes back, so the newest one from the same signin is revoked too.
+    let (status, body) = refresh(&server, &first["refreshToken"]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["code"], "unauthorized");
+    let (status, _) = refresh(&server, &second["refreshToken"]).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Other signins of the same member keep working.
+    let (status, _) = refresh(&server, &other["refreshToken"]).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn unknown_refresh_tokens_are_rejected() {
+    let server = Server::start(&[]);
+    let (status, _) = refresh(&server, &json!("not-a-refresh-token")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
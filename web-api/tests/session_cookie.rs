@@ -0,0 +1,85 @@
+mod support;
+
+use reqwest::{RequestBuilder, StatusCode, header};
+use serde_json::json;
+use support::{PASSWORD, Server, json_body};
+
+// The session cookies set by a cookie signin, as `name=value` pairs.
+async fn signin_cookies(server: &Server, account: &str) -> Vec<(String, String)> {
+    let res = server
+        .post("/service/auth/signin")
+        .json(&json!({ "account": account, "password": PASSWORD, "cookie": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    res.headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .map(|value| {
+            let pair = value.to_str().unwrap().split(';').next().unwrap();
+            let (name, value) = pair.split_once('=').unwrap();
+            (name.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+fn with_cookies(request: RequestBuilder, cookies: &[(String, String)]) -> RequestBuilder {
+    let header: Vec<String> = cookies.iter().map(|(n, v)| format!("{n}={v}")).collect();
+    request.header(header::COOKIE, header.join("; "))
+}
+
+fn create_todo(server: &Server, cookies: &[(String, String)]) -> RequestBuilder {
+    let request = server.post("/service/manage/todo").json(&json!({
+        "dueDate": "2030-01-01T00:00:00Z",
+        "content": "from the web UI",
+        "complete": false,
+        "public": false,
+    }));
+    with_cookies(request, cookies)
+}
+
+#[tokio::test]
+async fn cookie_sessions_need_the_csrf_token_for_state_changes() {
+    let server = Server::start(&["--session-cookie"]);
+    assert_eq!(server.signup("browser").await.status(), StatusCode::OK);
+    let cookies = signin_cookies(&server, "browser").await;
+    let csrf = cookies
+        .iter()
+        .find(|(name, _)| name.ends_with("_csrf"))
+        .map(|(_, value)| value.clone())
+        .unwrap();
+
+    // Reads need no token.
+    let res = with_cookies(server.get("/service/manage/tags"), &cookies)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = create_todo(&server, &cookies).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    assert_eq!(json_body(res).await["code"], "forbidden");
+
+    let res = create_todo(&server, &cookies)
+        .header("x-csrf-token", "0".repeat(csrf.len()))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = create_todo(&server, &cookies)
+        .header("x-csrf-token", &csrf)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(json_body(res).await["content"], "from the web UI");
+}
+
+#[tokio::test]
+async fn bearer_requests_need_no_csrf_token() {
+    let server = Server::start(&["--session-cookie"]);
+    let token = server.member("script").await;
+    server.create_todo(&token, json!({})).await;
+}
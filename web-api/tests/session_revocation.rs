@@ -0,0 +1,130 @@
+mod support;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use support::{PASSWORD, Server, json_body};
+
+fn issued_at(token: &str) -> i64 {
+    let payload = token.split('.').nth(1).unwrap();
+    let claims: Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+    claims["iat"].as_i64().unwrap()
+}
+
+async fn accepted(server: &Server, token: &str) -> bool {
+    let res = server
+        .get("/service/auth/totp")
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    res.status() == StatusCode::OK
+}
+
+// Revocation compares below whole seconds, so both edges are checked with tokens issued in
+// the same second as the revocation; a few accounts are tried until that happens.
+#[tokio::test]
+async fn a_password_change_keeps_only_the_new_pair() {
+    let server = Server::start(&[]);
+    let mut same_second = false;
+    for n in 0..5 {
+        let account = format!("changer{n}");
+        let current = server.member(&account).await;
+        let other = server.token(&account).await;
+
+        let res = server
+            .post("/service/auth/password")
+            .bearer_auth(&current)
+            .json(&json!({
+                "currentPassword": PASSWORD,
+                "newPassword": "Pw-Orbit-2",
+                "confirmedPassword": "Pw-Orbit-2",
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let replacement = json_body(res).await["token"].as_str().unwrap().to_string();
+
+        assert!(!accepted(&server, &current).await);
+        assert!(!accepted(&server, &other).await);
+        assert!(accepted(&server, &replacement).await);
+        if issued_at(&other) == issued_at(&replacement) {
+            same_second = true;
+            break;
+        }
+    }
+    assert!(
+        same_second,
+        "no token was issued in the second of its revocation"
+    );
+}
+
+#[tokio::test]
+async fn a_signin_right_after_signout_all_is_accepted() {
+    let server = Server::start(&[]);
+    let mut same_second = false;
+    for n in 0..5 {
+        let account = format!("leaver{n}");
+        let old = server.member(&account).await;
+
+        let res = server
+            .post("/service/auth/signout-all")
+            .bearer_auth(&old)
+            .send()
+            .await
+            .unwrap();
+        assert!(res.status().is_success());
+        let new = server.token(&account).await;
+
+        assert!(!accepted(&server, &old).await);
+        assert!(accepted(&server, &new).await);
+        if issued_at(&old) == issued_at(&new) {
+            same_second = true;
+            break;
+        }
+    }
+    assert!(
+        same_second,
+        "no token was issued in the second of its revocation"
+    );
+}
+
+#[tokio::test]
+async fn signout_revokes_only_the_presented_token_and_its_refresh_family() {
+    let server = Server::start(&[]);
+    server.member("commuter").await;
+    let res = server.signin("commuter").await;
+    let laptop = json_body(res).await;
+    let phone = server.token("commuter").await;
+    let laptop_token = laptop["token"].as_str().unwrap();
+    assert!(accepted(&server, laptop_token).await);
+
+    let res = server
+        .post("/service/auth/signout")
+        .bearer_auth(laptop_token)
+        .json(&json!({ "refreshToken": laptop["refreshToken"] }))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+
+    assert!(!accepted(&server, laptop_token).await);
+    assert!(accepted(&server, &phone).await);
+    let res = server
+        .post("/service/auth/refresh")
+        .json(&json!({ "refreshToken": laptop["refreshToken"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // A token whose jti is revoked cannot sign out again either.
+    let res = server
+        .post("/service/auth/signout")
+        .bearer_auth(laptop_token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
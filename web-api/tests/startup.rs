@@ -0,0 +1,25 @@
+use std::process::Command;
+
+#[test]
+fn a_missing_migration_directory_stops_startup() {
+    let dir = std::env::temp_dir().join(format!("web-api-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dsn = format!("sqlite://{}?mode=rwc", dir.join("test.db").display());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_web-api"))
+        .current_dir(&dir)
+        .args(["--dsn", &dsn, "--migration", "no-such-migrations"])
+        .args([
+            "--host",
+            "127.0.0.1:0",
+            "--jwt-secret",
+            "integration-test-secret",
+        ])
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no-such-migrations"), "{stderr}");
+}
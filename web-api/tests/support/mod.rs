@@ -0,0 +1,248 @@
+// Each test binary uses a different part of this module.
+#![allow(dead_code)]
+
+use reqwest::{Client, RequestBuilder, Response, StatusCode, redirect::Policy};
+use serde_json::{Value, json};
+use std::fs::{self, OpenOptions};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+pub const PASSWORD: &str = "Pw-Orbit-1";
+
+// Where `Server::start(MAILBOX_ARGS)` has the file notifier write, relative to its directory.
+pub const MAILBOX: &str = "mailbox.jsonl";
+pub const MAILBOX_ARGS: [&str; 4] = ["--notifier", "file", "--notifier-file", MAILBOX];
+
+// A web-api process on a free local port with a SQLite database of its own. The process
+// runs in a scratch directory so only a web-api.config.yaml a test writes there is picked
+// up; both are removed when the server is dropped.
+pub struct Server {
+    child: Child,
+    dir: PathBuf,
+    dsn: String,
+    pub base: String,
+    pub client: Client,
+}
+
+impl Server {
+    pub fn start(args: &[&str]) -> Self {
+        let dir = std::env::temp_dir().join(format!("web-api-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let dsn = format!("sqlite://{}?mode=rwc", dir.join("test.db").display());
+        let (child, host) = spawn(&dir, &dsn, args);
+        let mut server = Self {
+            child,
+            dir,
+            dsn,
+            base: format!("http://{host}"),
+            client: Client::builder().redirect(Policy::none()).build().unwrap(),
+        };
+        server.wait_until_listening(&host);
+        server
+    }
+
+    // Stops the process and starts a new one with `args` on the same directory and database,
+    // e.g. after writing a web-api.config.yaml there.
+    pub fn restart(&mut self, args: &[&str]) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let (child, host) = spawn(&self.dir, &self.dsn, args);
+        self.child = child;
+        self.base = format!("http://{host}");
+        self.wait_until_listening(&host);
+    }
+
+    fn wait_until_listening(&mut self, host: &str) {
+        let deadline = Instant::now() + Duration::from_secs(20);
+        while TcpStream::connect(host).is_err() {
+            if let Some(status) = self.child.try_wait().unwrap() {
+                panic!("web-api exited with {status}:\n{}", self.log());
+            }
+            assert!(
+                Instant::now() < deadline,
+                "web-api did not start:\n{}",
+                self.log()
+            );
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    // Runs `--grant-admin` against the same database; the member has to sign in again.
+    pub fn grant_admin(&self, account: &str) {
+        let output = Command::new(env!("CARGO_BIN_EXE_web-api"))
+            .current_dir(&self.dir)
+            .args([
+                "--dsn",
+                &self.dsn,
+                "--jwt-secret",
+                "integration-test-secret",
+            ])
+            .args(["--grant-admin", account])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    pub fn dsn(&self) -> &str {
+        &self.dsn
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn log(&self) -> String {
+        fs::read_to_string(self.dir.join("server.log")).unwrap_or_default()
+    }
+
+    // Notifications are delivered in the background, so this waits for the first `count`.
+    pub async fn mails(&self, count: usize) -> Vec<Value> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let mails: Vec<Value> = fs::read_to_string(self.dir.join(MAILBOX))
+                .unwrap_or_default()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect();
+            if mails.len() >= count {
+                return mails;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "expected {count} mails, got {mails:?}"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.client.get(self.url(path))
+    }
+
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.client.post(self.url(path))
+    }
+
+    pub fn put(&self, path: &str) -> RequestBuilder {
+        self.client.put(self.url(path))
+    }
+
+    pub fn patch(&self, path: &str) -> RequestBuilder {
+        self.client.patch(self.url(path))
+    }
+
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.client.delete(self.url(path))
+    }
+
+    pub async fn signup(&self, account: &str) -> Response {
+        self.post("/service/auth/signup")
+            .json(&json!({
+                "account": account,
+                "password": PASSWORD,
+                "confirmedPassword": PASSWORD,
+            }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn signin(&self, account: &str) -> Response {
+        self.post("/service/auth/signin")
+            .json(&json!({ "account": account, "password": PASSWORD }))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    // Signs up and returns an access token.
+    pub async fn member(&self, account: &str) -> String {
+        assert_eq!(self.signup(account).await.status(), StatusCode::OK);
+        self.token(account).await
+    }
+
+    // Creates a todo from defaults overridden by `fields` and returns its id.
+    pub async fn create_todo(&self, token: &str, fields: Value) -> i64 {
+        let mut body = json!({
+            "dueDate": "2030-01-01T00:00:00Z",
+            "content": "todo",
+            "complete": false,
+            "public": false,
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        let res = self
+            .post("/service/manage/todo")
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK, "create {body}");
+        json_body(res).await["id"].as_i64().unwrap()
+    }
+
+    pub async fn token(&self, account: &str) -> String {
+        let res = self.signin(account).await;
+        assert_eq!(res.status(), StatusCode::OK, "signin of {account}");
+        json_body(res).await["token"].as_str().unwrap().to_string()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+// Spawns web-api on a free local port in `dir` and returns the process with its address.
+fn spawn(dir: &Path, dsn: &str, args: &[&str]) -> (Child, String) {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let host = format!("127.0.0.1:{port}");
+    let migration = concat!(env!("CARGO_MANIFEST_DIR"), "/../migrations/sqlite");
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("server.log"))
+        .unwrap();
+
+    let child = Command::new(env!("CARGO_BIN_EXE_web-api"))
+        .current_dir(dir)
+        .args(["--dsn", dsn, "--migration", migration, "--host", &host])
+        .args([
+            "--jwt-secret",
+            "integration-test-secret",
+            "--log-level",
+            "warn",
+        ])
+        // Keeps argon2 fast in debug builds
+        .args(["--password-hash-memory-kib", "1024"])
+        .args(["--password-hash-iterations", "1"])
+        .args(args)
+        .stdout(log.try_clone().unwrap())
+        .stderr(log)
+        .spawn()
+        .unwrap();
+    (child, host)
+}
+
+pub async fn json_body(res: Response) -> Value {
+    res.json().await.unwrap()
+}
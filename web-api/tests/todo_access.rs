@@ -0,0 +1,99 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use support::{Server, json_body};
+
+#[tokio::test]
+async fn private_todos_of_others_look_like_missing_ones() {
+    let server = Server::start(&[]);
+    let owner = server.member("owner").await;
+    let other = server.member("other").await;
+    let private = server
+        .create_todo(&owner, json!({ "content": "secret plan" }))
+        .await;
+    let missing = private + 1000;
+
+    for id in [private, missing] {
+        let find = server
+            .get(&format!("/service/todo/{id}"))
+            .bearer_auth(&other)
+            .send()
+            .await
+            .unwrap();
+        let tree = server
+            .get(&format!("/service/todo/{id}/tree"))
+            .bearer_auth(&other)
+            .send()
+            .await
+            .unwrap();
+        let patch = server
+            .patch(&format!("/service/manage/todo/{id}"))
+            .bearer_auth(&other)
+            .json(&json!({ "complete": true }))
+            .send()
+            .await
+            .unwrap();
+        let put = server
+            .put(&format!("/service/manage/todo/{id}"))
+            .bearer_auth(&other)
+            .json(&json!({
+                "dueDate": "2030-01-01T00:00:00Z",
+                "content": "mine now",
+                "complete": false,
+            }))
+            .send()
+            .await
+            .unwrap();
+        let delete = server
+            .delete(&format!("/service/manage/todo/{id}"))
+            .bearer_auth(&other)
+            .send()
+            .await
+            .unwrap();
+        for res in [find, tree] {
+            assert_eq!(res.status(), StatusCode::OK, "todo {id}");
+            assert_eq!(json_body(res).await, Value::Null, "todo {id}");
+        }
+        for res in [patch, put, delete] {
+            assert_eq!(res.status(), StatusCode::NOT_FOUND, "todo {id}");
+            assert_eq!(json_body(res).await["code"], "not_found", "todo {id}");
+        }
+    }
+
+    // The owner still sees the untouched todo.
+    let res = server
+        .get(&format!("/service/todo/{private}"))
+        .bearer_auth(&owner)
+        .send()
+        .await
+        .unwrap();
+    let todo = json_body(res).await;
+    assert_eq!(todo["content"], "secret plan");
+    assert_eq!(todo["complete"], false);
+}
+
+#[tokio::test]
+async fn public_todos_of_others_are_read_only() {
+    let server = Server::start(&[]);
+    let owner = server.member("owner").await;
+    let other = server.member("other").await;
+    let public = server.create_todo(&owner, json!({ "public": true })).await;
+
+    let res = server
+        .get(&format!("/service/todo/{public}"))
+        .bearer_auth(&other)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(json_body(res).await["id"], public);
+
+    let res = server
+        .patch(&format!("/service/manage/todo/{public}"))
+        .bearer_auth(&other)
+        .json(&json!({ "complete": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,113 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use support::{Server, json_body};
+
+async fn patch(server: &Server, token: &str, id: i64, body: Value) -> (StatusCode, Value) {
+    let res = server
+        .patch(&format!("/service/manage/todo/{id}"))
+        .bearer_auth(token)
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    let status = res.status();
+    (status, json_body(res).await)
+}
+
+async fn find(server: &Server, token: &str, id: i64) -> Value {
+    let res = server
+        .get(&format!("/service/todo/{id}"))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    json_body(res).await
+}
+
+#[tokio::test]
+async fn todos_cannot_become_their_own_ancestors() {
+    let server = Server::start(&[]);
+    let token = server.member("planner").await;
+    let a = server.create_todo(&token, json!({})).await;
+    let b = server.create_todo(&token, json!({ "parentId": a })).await;
+
+    let (status, body) = patch(&server, &token, a, json!({ "parentId": a })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["detail"]
+            .as_str()
+            .unwrap()
+            .contains("subtask of itself"),
+        "{body}"
+    );
+
+    // b is already below a, so a cannot move below b.
+    let (status, _) = patch(&server, &token, a, json!({ "parentId": b })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(find(&server, &token, a).await["parentId"], Value::Null);
+
+    // Moving b back to the top level and then a below it is fine.
+    let (status, _) = patch(&server, &token, b, json!({ "parentId": null })).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = patch(&server, &token, a, json!({ "parentId": b })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["parentId"], b);
+}
+
+#[tokio::test]
+async fn parents_complete_with_their_last_subtask() {
+    let server = Server::start(&[]);
+    let token = server.member("roller").await;
+    let root = server.create_todo(&token, json!({})).await;
+    let parent = server
+        .create_todo(&token, json!({ "parentId": root }))
+        .await;
+    let first = server
+        .create_todo(&token, json!({ "parentId": parent }))
+        .await;
+    let second = server
+        .create_todo(&token, json!({ "parentId": parent }))
+        .await;
+
+    patch(&server, &token, first, json!({ "complete": true })).await;
+    assert_eq!(find(&server, &token, parent).await["complete"], false);
+
+    let (status, _) = patch(&server, &token, second, json!({ "complete": true })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(find(&server, &token, parent).await["complete"], true);
+    assert_eq!(find(&server, &token, root).await["complete"], true);
+
+    // A new open subtask reopens everything above it.
+    server
+        .create_todo(&token, json!({ "parentId": parent }))
+        .await;
+    assert_eq!(find(&server, &token, parent).await["complete"], false);
+    assert_eq!(find(&server, &token, root).await["complete"], false);
+}
+
+#[tokio::test]
+async fn checklists_keep_their_order_and_checked_items() {
+    let server = Server::start(&[]);
+    let token = server.member("checker").await;
+    let checklist = json!([
+        { "content": "pack", "checked": false },
+        { "content": "travel", "checked": false },
+    ]);
+    let id = server
+        .create_todo(&token, json!({ "checklist": checklist }))
+        .await;
+    assert_eq!(find(&server, &token, id).await["checklist"], checklist);
+
+    let done = json!([
+        { "content": "pack", "checked": true },
+        { "content": "travel", "checked": true },
+    ]);
+    let (status, body) = patch(&server, &token, id, json!({ "checklist": done })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["checklist"], done);
+    // Checked items do not complete the todo; only subtasks roll up.
+    assert_eq!(body["complete"], false);
+    assert_eq!(find(&server, &token, id).await["checklist"], done);
+}
@@ -0,0 +1,59 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::json;
+use support::{Server, json_body};
+
+async fn list(server: &Server, token: &str, query: &str) -> reqwest::Response {
+    server
+        .get(&format!("/service/todo?{query}"))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn cursors_only_continue_their_own_listing() {
+    let server = Server::start(&[]);
+    let token = server.member("pager").await;
+    for (day, complete) in [(3, false), (1, false), (2, true), (4, false)] {
+        let due_date = format!("2030-01-0{day}T00:00:00Z");
+        server
+            .create_todo(&token, json!({ "dueDate": due_date, "complete": complete }))
+            .await;
+    }
+
+    let res = list(&server, &token, "sort=dueDate&limit=2").await;
+    let page = json_body(res).await;
+    assert_eq!(page["hasMore"], true);
+    let cursor = page["nextCursor"].as_str().unwrap().to_string();
+
+    let res = list(
+        &server,
+        &token,
+        &format!("sort=dueDate&limit=2&cursor={cursor}"),
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let page = json_body(res).await;
+    let due_dates: Vec<_> = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["dueDate"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(due_dates.len(), 2);
+    assert!(due_dates[0].starts_with("2030-01-03"), "{due_dates:?}");
+    assert!(due_dates[1].starts_with("2030-01-04"), "{due_dates:?}");
+
+    for query in [
+        "sort=id&limit=2",
+        "sort=dueDate&order=desc&limit=2",
+        "sort=dueDate&complete=false&limit=2",
+        "sort=dueDate&limit=2&offset=1",
+    ] {
+        let res = list(&server, &token, &format!("{query}&cursor={cursor}")).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{query}");
+    }
+}
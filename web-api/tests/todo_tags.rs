@@ -0,0 +1,76 @@
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::json;
+use support::{Server, json_body};
+
+async fn ids_tagged(server: &Server, token: &str, query: &str) -> Vec<i64> {
+    let res = server
+        .get(&format!("/service/todo?account=tagger&sort=id&{query}"))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK, "{query}");
+    json_body(res).await["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_i64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn tag_filters_match_all_or_any_of_the_tags() {
+    let server = Server::start(&[]);
+    let token = server.member("tagger").await;
+    for name in ["work", "urgent", "home"] {
+        let res = server
+            .post("/service/manage/tags")
+            .bearer_auth(&token)
+            .json(&json!({ "name": name }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+    let mut ids = Vec::new();
+    for tags in [
+        json!(["work", "urgent"]),
+        json!(["work"]),
+        json!(["urgent"]),
+        json!(["home"]),
+        json!([]),
+    ] {
+        ids.push(server.create_todo(&token, json!({ "tags": tags })).await);
+    }
+
+    // `all` is the default.
+    let both = ids_tagged(&server, &token, "tags=work,urgent").await;
+    assert_eq!(both, [ids[0]]);
+    let all = ids_tagged(&server, &token, "tags=urgent,work&tagMatch=all").await;
+    assert_eq!(all, [ids[0]]);
+    let any = ids_tagged(&server, &token, "tags=work,urgent&tagMatch=any").await;
+    assert_eq!(any, [ids[0], ids[1], ids[2]]);
+    let one = ids_tagged(&server, &token, "tags=home&tagMatch=all").await;
+    assert_eq!(one, [ids[3]]);
+    let untagged = ids_tagged(&server, &token, "").await;
+    assert_eq!(untagged, ids);
+
+    // Tags belong to one member; another member cannot assign them.
+    let other = server.member("other").await;
+    let res = server
+        .post("/service/manage/todo")
+        .bearer_auth(&other)
+        .json(&json!({
+            "dueDate": "2030-01-01T00:00:00Z",
+            "content": "todo",
+            "complete": false,
+            "public": true,
+            "tags": ["work"],
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,58 @@
+mod support;
+
+use reqwest::{StatusCode, header};
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+use support::{PASSWORD, Server, json_body};
+
+fn current_code(secret: &str) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    simple_totp::code_at(secret, now.as_secs()).unwrap()
+}
+
+#[tokio::test]
+async fn reauthentication_failures_are_throttled() {
+    let server = Server::start(&["--throttle-free-attempts", "1"]);
+    let token = server.member("guarded").await;
+
+    let res = server
+        .post("/service/auth/totp")
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    let secret = json_body(res).await["secret"].as_str().unwrap().to_string();
+    let res = server
+        .post("/service/auth/totp/confirm")
+        .bearer_auth(&token)
+        .json(&json!({ "code": current_code(&secret) }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    for _ in 0..2 {
+        let res = server
+            .post("/service/auth/totp/recovery-codes")
+            .bearer_auth(&token)
+            .json(&json!({ "password": "wrong-password", "code": "000000" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Even the right credentials wait out the backoff.
+    let res = server
+        .delete("/service/auth/totp")
+        .bearer_auth(&token)
+        .json(&json!({ "password": PASSWORD, "code": current_code(&secret) }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(res.headers().contains_key(header::RETRY_AFTER));
+
+    let res = server.signin("guarded").await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+}